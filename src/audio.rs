@@ -2,6 +2,14 @@
 // Handles sound effects for correct/wrong answers, marking, and menu navigation
 
 use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turn all sound effects on or off
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
 
 /// Play the correct answer sound effect
 pub fn play_correct_sound() {
@@ -39,8 +47,27 @@ pub fn play_menu_sound() {
     });
 }
 
+/// Play the celebration for reaching the daily goal
+pub fn play_goal_sound() {
+    std::thread::spawn(|| {
+        for sound in [
+            include_bytes!("../assets/sounds/correct.mp3").as_slice(),
+            include_bytes!("../assets/sounds/mark.mp3").as_slice(),
+        ] {
+            if let Err(e) = play_sound_internal(sound.to_vec()) {
+                eprintln!("Failed to play goal sound: {}", e);
+                break;
+            }
+        }
+    });
+}
+
 fn play_sound_internal(audio_data: Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
     use rodio::{Decoder, OutputStream, Sink};
+
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
     
     // Get an output stream handle
     let (_stream, stream_handle) = OutputStream::try_default()?;
@@ -0,0 +1,133 @@
+// Config file
+// Optional INI-style file next to the database with key bindings and themes
+
+use anyhow::{Result, anyhow, bail};
+use std::fs;
+use std::io::ErrorKind;
+
+pub const CONFIG_PATH: &str = "vocabulator.conf";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    path: String,
+    sections: Vec<(String, Vec<Entry>)>,
+}
+
+impl Config {
+    /// Read the config file; a missing file is an empty config.
+    pub fn load(path: &str) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(path, &text),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => bail!("{path}: {e}"),
+        }
+    }
+
+    /// Parse `[section]` headers and `key = value` lines; `#` starts a
+    /// comment line.
+    pub fn parse(path: &str, text: &str) -> Result<Self> {
+        let mut config = Self {
+            path: path.to_string(),
+            sections: Vec::new(),
+        };
+
+        for (i, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                config.sections.push((name.trim().to_string(), Vec::new()));
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                bail!("{path}:{}: expected `key = value`", i + 1);
+            };
+            let Some((_, entries)) = config.sections.last_mut() else {
+                bail!("{path}:{}: `{}` is outside a [section]", i + 1, key.trim());
+            };
+            entries.push(Entry {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+                line: i + 1,
+            });
+        }
+
+        Ok(config)
+    }
+
+    /// Entries of every section called `name`, in file order.
+    pub fn section(&self, name: &str) -> impl Iterator<Item = &Entry> {
+        self.sections
+            .iter()
+            .filter(move |(n, _)| n == name)
+            .flat_map(|(_, entries)| entries)
+    }
+
+    /// Names of all sections, each once, in file order.
+    pub fn section_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (name, _) in &self.sections {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// An error pointing at the line of `entry`.
+    pub fn error(&self, entry: &Entry, message: impl std::fmt::Display) -> anyhow::Error {
+        anyhow!("{}:{}: {message}", self.path, entry.line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sections() {
+        let config = Config::parse(
+            "test.conf",
+            "# comment\n[keys]\npreset = vim\n\n[other]\nx=1\n[keys]\nmenu.down = s\n",
+        )
+        .unwrap();
+
+        let keys: Vec<(&str, &str, usize)> = config
+            .section("keys")
+            .map(|e| (e.key.as_str(), e.value.as_str(), e.line))
+            .collect();
+        assert_eq!(keys, [("preset", "vim", 3), ("menu.down", "s", 8)]);
+        assert_eq!(config.section("missing").count(), 0);
+        assert_eq!(config.section_names(), ["keys", "other"]);
+    }
+
+    #[test]
+    fn test_errors_have_line_numbers() {
+        let err = Config::parse("test.conf", "[keys]\nnonsense\n").unwrap_err();
+        assert_eq!(err.to_string(), "test.conf:2: expected `key = value`");
+
+        let err = Config::parse("test.conf", "a = b\n").unwrap_err();
+        assert_eq!(err.to_string(), "test.conf:1: `a` is outside a [section]");
+    }
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("none.conf");
+
+        assert_eq!(
+            Config::load(path.to_str().unwrap()).unwrap(),
+            Config::default()
+        );
+    }
+}
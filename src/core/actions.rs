@@ -1,6 +1,8 @@
-use crate::core::{progress, session};
+use crate::audio;
+use crate::core::{goal, progress, session};
 use crate::ui::app::{App, Screen};
 use anyhow::{Result, anyhow};
+use chrono::Local;
 
 pub fn handle_enter(app: &mut App) -> Result<()> {
     let session = app.session.as_mut().ok_or_else(|| anyhow!("No session"))?;
@@ -11,8 +13,18 @@ pub fn handle_enter(app: &mut App) -> Result<()> {
 
     let correct = session.graded.unwrap();
     let word = session.current_mut();
+    let today = Local::now().date_naive();
+    let before = goal::Status::compute(&app.conn, today)?;
     progress::update_word_stats(&app.conn, word, correct)?;
+    progress::record_review(&app.conn, word, correct)?;
 
+    let after = goal::Status::compute(&app.conn, today)?;
+    if after.reached() && !before.reached() {
+        audio::play_goal_sound();
+    }
+    app.goal = Some(after);
+
+    session.answered += 1;
     let finished = session.advance();
 
     if session.session_type == session::Type::Group {
@@ -26,7 +38,10 @@ pub fn handle_enter(app: &mut App) -> Result<()> {
         )?;
     }
 
-    if finished {
+    if session.limit_reached() {
+        app.session = None;
+        app.current_screen = Screen::Menu;
+    } else if finished {
         if app.current_screen == Screen::Test {
             app.current_screen = Screen::Menu;
         } else {
@@ -0,0 +1,227 @@
+use crate::db::queries;
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use clap::ValueEnum;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+const KIND_KEY: &str = "goal_kind";
+const TARGET_KEY: &str = "goal_target";
+
+/// What counts towards the daily goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+    /// Graded answers
+    Reviews,
+    /// Words answered for the first time
+    NewWords,
+}
+
+impl Kind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Kind::Reviews => "reviews",
+            Kind::NewWords => "new words",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Goal {
+    pub kind: Kind,
+    pub target: u32,
+}
+
+impl Default for Goal {
+    fn default() -> Self {
+        Self {
+            kind: Kind::Reviews,
+            target: 20,
+        }
+    }
+}
+
+impl Goal {
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let default = Self::default();
+        let kind = match queries::fetch_state_value(conn, KIND_KEY)? {
+            Some(1) => Kind::NewWords,
+            Some(_) => Kind::Reviews,
+            None => default.kind,
+        };
+        let target = queries::fetch_state_value(conn, TARGET_KEY)?
+            .and_then(|t| u32::try_from(t).ok())
+            .filter(|t| *t > 0)
+            .unwrap_or(default.target);
+        Ok(Self { kind, target })
+    }
+
+    pub fn save(&self, conn: &Connection) -> Result<()> {
+        let kind = match self.kind {
+            Kind::Reviews => 0,
+            Kind::NewWords => 1,
+        };
+        queries::set_app_state(conn, KIND_KEY, kind)?;
+        queries::set_app_state(conn, TARGET_KEY, self.target as i32)
+    }
+}
+
+/// Today's progress towards the goal and the streaks of days meeting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status {
+    pub goal: Goal,
+    pub today: u32,
+    /// Days in a row the goal was met, ending today, or yesterday while
+    /// today's goal is still open
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+impl Status {
+    pub fn compute(conn: &Connection, today: NaiveDate) -> Result<Self> {
+        let goal = Goal::load(conn)?;
+        let days: HashMap<NaiveDate, u32> = queries::fetch_daily_activity(conn)?
+            .into_iter()
+            .filter_map(|(day, reviews, new_words)| {
+                let date = NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?;
+                let count = match goal.kind {
+                    Kind::Reviews => reviews,
+                    Kind::NewWords => new_words,
+                };
+                Some((date, count))
+            })
+            .collect();
+        Ok(Self::from_days(goal, &days, today))
+    }
+
+    pub fn from_days(goal: Goal, days: &HashMap<NaiveDate, u32>, today: NaiveDate) -> Self {
+        let met = |date: &NaiveDate| days.get(date).is_some_and(|c| *c >= goal.target);
+
+        let mut current_streak = 0;
+        let mut day = if met(&today) {
+            today
+        } else {
+            today - Duration::days(1)
+        };
+        while met(&day) {
+            current_streak += 1;
+            day -= Duration::days(1);
+        }
+
+        let mut met_days: Vec<NaiveDate> = days.keys().filter(|d| met(d)).copied().collect();
+        met_days.sort();
+        let mut longest_streak = 0;
+        let mut run = 0;
+        for (i, day) in met_days.iter().enumerate() {
+            run = match i.checked_sub(1).map(|p| met_days[p]) {
+                Some(previous) if *day - previous == Duration::days(1) => run + 1,
+                _ => 1,
+            };
+            longest_streak = longest_streak.max(run);
+        }
+
+        Self {
+            goal,
+            today: days.get(&today).copied().unwrap_or(0),
+            current_streak,
+            longest_streak,
+        }
+    }
+
+    pub fn reached(&self) -> bool {
+        self.today >= self.goal.target
+    }
+
+    /// Progress towards today's goal, from 0 to 1.
+    pub fn ratio(&self) -> f64 {
+        (self.today as f64 / self.goal.target as f64).min(1.0)
+    }
+
+    /// One-line summary, e.g. "12/20 reviews today | streak 3 days (best 7)".
+    pub fn summary(&self) -> String {
+        format!(
+            "{}/{} {} today | streak {} (best {})",
+            self.today,
+            self.goal.target,
+            self.goal.kind.label(),
+            days(self.current_streak),
+            self.longest_streak
+        )
+    }
+}
+
+fn days(n: u32) -> String {
+    if n == 1 {
+        "1 day".into()
+    } else {
+        format!("{n} days")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, d).unwrap()
+    }
+
+    fn goal(target: u32) -> Goal {
+        Goal {
+            kind: Kind::Reviews,
+            target,
+        }
+    }
+
+    #[test]
+    fn test_streak_survives_until_today_is_over() {
+        let days = HashMap::from([(date(10), 5), (date(11), 6), (date(12), 2)]);
+
+        let status = Status::from_days(goal(5), &days, date(12));
+
+        assert_eq!(status.today, 2);
+        assert!(!status.reached());
+        assert_eq!((status.current_streak, status.longest_streak), (2, 2));
+    }
+
+    #[test]
+    fn test_missed_day_breaks_the_streak() {
+        let days = HashMap::from([
+            (date(1), 5),
+            (date(2), 5),
+            (date(3), 5),
+            (date(5), 5),
+            (date(6), 9),
+        ]);
+
+        let status = Status::from_days(goal(5), &days, date(6));
+
+        assert!(status.reached());
+        assert_eq!((status.current_streak, status.longest_streak), (2, 3));
+        assert_eq!(
+            status.summary(),
+            "9/5 reviews today | streak 2 days (best 3)"
+        );
+    }
+
+    #[test]
+    fn test_goal_and_activity_are_stored() {
+        let conn = init_db(":memory:").unwrap();
+        assert_eq!(Goal::load(&conn).unwrap(), Goal::default());
+
+        let new_words = Goal {
+            kind: Kind::NewWords,
+            target: 2,
+        };
+        new_words.save(&conn).unwrap();
+        queries::record_activity(&conn, "2024-05-12", true).unwrap();
+        queries::record_activity(&conn, "2024-05-12", false).unwrap();
+        queries::record_activity(&conn, "2024-05-12", true).unwrap();
+
+        let status = Status::compute(&conn, date(12)).unwrap();
+        assert_eq!(status.goal, new_words);
+        assert_eq!(status.today, 2);
+        assert_eq!(status.current_streak, 1);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::db::queries;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
+use rusqlite::Connection;
+use std::io::Write;
+
+/// Weeks shown, enough to always cover the past year.
+pub const WEEKS: usize = 53;
+
+/// Characters for activity levels 0 to 4 in the text heatmap.
+pub const LEVEL_CHARS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Row labels, Sunday first; every other day is left blank.
+pub const WEEKDAY_LABELS: [&str; 7] = ["", "Mon", "", "Wed", "", "Fri", ""];
+
+/// Reviews per day over the past year, laid out in weeks starting on Sunday.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    /// First day shown, always a Sunday
+    pub start: NaiveDate,
+    /// Reviews on each day from `start` through today
+    pub counts: Vec<u32>,
+}
+
+impl Heatmap {
+    pub fn compute(conn: &Connection, today: NaiveDate) -> Result<Self> {
+        let start = start_date(today);
+        let since = start
+            .and_hms_opt(0, 0, 0)
+            .and_then(|t| t.and_local_timezone(Local).earliest())
+            .map_or(0, |t| t.timestamp());
+        Ok(Self::from_timestamps(
+            queries::fetch_review_times(conn, since)?,
+            today,
+        ))
+    }
+
+    pub fn from_timestamps(timestamps: impl IntoIterator<Item = i64>, today: NaiveDate) -> Self {
+        let start = start_date(today);
+        let mut counts = vec![0; (today - start).num_days() as usize + 1];
+        for ts in timestamps {
+            let Some(time) = DateTime::from_timestamp(ts, 0) else {
+                continue;
+            };
+            let day = (time.with_timezone(&Local).date_naive() - start).num_days();
+            if let Some(count) = usize::try_from(day).ok().and_then(|d| counts.get_mut(d)) {
+                *count += 1;
+            }
+        }
+        Self { start, counts }
+    }
+
+    /// Reviews on the day in `week` (0 is the oldest) and `weekday` (0 is
+    /// Sunday), or `None` for days after today.
+    pub fn get(&self, week: usize, weekday: usize) -> Option<u32> {
+        self.counts.get(week * 7 + weekday).copied()
+    }
+
+    /// Reviews on each of the last `days` days, oldest first.
+    pub fn last_days(&self, days: usize) -> &[u32] {
+        &self.counts[self.counts.len().saturating_sub(days)..]
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    pub fn active_days(&self) -> usize {
+        self.counts.iter().filter(|c| **c > 0).count()
+    }
+
+    /// 0 for no reviews, otherwise 1 to 4 by quarter of the busiest day.
+    pub fn level(&self, count: u32) -> usize {
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        if count == 0 || max == 0 {
+            return 0;
+        }
+        ((count * 4).div_ceil(max) as usize).clamp(1, 4)
+    }
+
+    /// Short month names with the week column in which each month starts.
+    pub fn month_labels(&self) -> Vec<(usize, String)> {
+        let mut labels = Vec::new();
+        for week in 0..WEEKS {
+            let first = self.start + Duration::weeks(week as i64);
+            let last = first + Duration::days(6);
+            if week == 0 || last.month() != (last - Duration::days(7)).month() {
+                labels.push((week, last.format("%b").to_string()));
+            }
+        }
+        // The first label is cut short by the second when they are too close
+        if labels.len() > 1 && labels[1].0 < 4 {
+            labels.remove(0);
+        }
+        labels
+    }
+
+    /// The month labels laid out above the week columns.
+    pub fn month_row(&self) -> String {
+        let mut row = vec![' '; WEEKS];
+        for (week, name) in self.month_labels() {
+            for (i, c) in name.chars().enumerate() {
+                if let Some(slot) = row.get_mut(week + i) {
+                    *slot = c;
+                }
+            }
+        }
+        row.into_iter().collect::<String>().trim_end().to_string()
+    }
+
+    pub fn write_text<W: Write>(&self, out: &mut W) -> Result<()> {
+        writeln!(out, "    {}", self.month_row())?;
+        for (weekday, label) in WEEKDAY_LABELS.iter().enumerate() {
+            let row: String = (0..WEEKS)
+                .map(|week| match self.get(week, weekday) {
+                    Some(count) => LEVEL_CHARS[self.level(count)],
+                    None => ' ',
+                })
+                .collect();
+            writeln!(out, "{label:<4}{}", row.trim_end())?;
+        }
+
+        writeln!(
+            out,
+            "\n    Less {} More",
+            LEVEL_CHARS
+                .iter()
+                .map(char::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        )?;
+        writeln!(
+            out,
+            "{} reviews in the last year, on {} days",
+            self.total(),
+            self.active_days()
+        )?;
+        Ok(())
+    }
+}
+
+/// The Sunday starting the oldest week shown.
+fn start_date(today: NaiveDate) -> NaiveDate {
+    let week_start = today - Duration::days(today.weekday().num_days_from_sunday() as i64);
+    week_start - Duration::weeks(WEEKS as i64 - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn at(day: NaiveDate) -> i64 {
+        day.and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .timestamp()
+    }
+
+    #[test]
+    fn test_layout_starts_on_sunday() {
+        // A Wednesday
+        let today = date(2024, 5, 15);
+        let heatmap = Heatmap::from_timestamps([], today);
+
+        assert_eq!(heatmap.start.weekday(), chrono::Weekday::Sun);
+        assert_eq!(heatmap.counts.len(), (WEEKS - 1) * 7 + 4);
+        assert_eq!(heatmap.get(WEEKS - 1, 3), Some(0));
+        assert_eq!(heatmap.get(WEEKS - 1, 4), None);
+    }
+
+    #[test]
+    fn test_counts_and_levels() {
+        let today = date(2024, 5, 15);
+        let heatmap = Heatmap::from_timestamps(
+            [
+                at(today),
+                at(today),
+                at(today),
+                at(today),
+                at(today - Duration::days(1)),
+                at(date(2020, 1, 1)),
+            ],
+            today,
+        );
+
+        assert_eq!(heatmap.last_days(3), &[0, 1, 4]);
+        assert_eq!((heatmap.total(), heatmap.active_days()), (5, 2));
+        assert_eq!(heatmap.level(0), 0);
+        assert_eq!(heatmap.level(1), 1);
+        assert_eq!(heatmap.level(4), 4);
+    }
+
+    #[test]
+    fn test_text_output() {
+        let today = date(2024, 5, 15);
+        let heatmap = Heatmap::from_timestamps([at(today)], today);
+        let mut out = Vec::new();
+
+        heatmap.write_text(&mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].contains("Jun") && lines[0].contains("May"));
+        assert!(lines[4].starts_with("Wed ·"));
+        assert!(lines[4].ends_with('█'));
+        assert!(text.ends_with("1 reviews in the last year, on 1 days\n"));
+    }
+}
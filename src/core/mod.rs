@@ -1,5 +1,9 @@
 pub mod actions;
+pub mod goal;
+pub mod heatmap;
 pub mod progress;
 pub mod session;
+pub mod settings;
+pub mod stats;
 pub mod tutorial;
 pub mod utils;
@@ -3,6 +3,7 @@ use crate::{
     ui::app::Screen,
 };
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use rusqlite::Connection;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -16,3 +17,18 @@ pub fn update_word_stats(conn: &Connection, word: &mut Word, correct: bool) -> R
     word.success_count += correct as u8;
     queries::update_word_stats(conn, &word)
 }
+
+/// Log a graded answer for the activity history and the daily goal. Call
+/// after `update_word_stats`, which sets the review time.
+pub fn record_review(conn: &Connection, word: &Word, correct: bool) -> Result<()> {
+    let reviewed_at = match word.last_seen {
+        Some(ts) => ts.into(),
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+    };
+    queries::insert_review(conn, word.id, reviewed_at, correct)?;
+
+    let day = DateTime::from_timestamp(reviewed_at, 0)
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    queries::record_activity(conn, &day, word.times_seen == 1)
+}
@@ -1,8 +1,12 @@
+use crate::core::settings::Settings;
 use crate::db::models::Word;
 use crate::db::queries;
 use crate::ui::app::Screen;
 use anyhow::Result;
 use rusqlite::Connection;
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
 
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
 pub enum Type {
@@ -10,7 +14,6 @@ pub enum Type {
     Group,
     Marked,
     Weak,
-    #[allow(dead_code)]
     Custom,
 }
 
@@ -30,6 +33,9 @@ impl Type {
 pub struct Session {
     pub words: Vec<Word>,
     pub index: usize,
+    /// Words answered before the session ends, `None` for no limit
+    pub limit: Option<usize>,
+    pub answered: usize,
 
     // UI state
     pub session_type: Type,
@@ -37,6 +43,34 @@ pub struct Session {
     pub graded: Option<bool>,
     pub input_buffer: String,
     pub insert_mode: bool,
+    pub scroll: Scroll,
+}
+
+/// How far the definition panel is scrolled. The panel records its height and
+/// how far it can go while drawing, so paging stops at the end.
+#[derive(Debug, Default)]
+pub struct Scroll {
+    pub offset: u16,
+    pub page: Cell<u16>,
+    pub max: Cell<u16>,
+}
+
+impl Scroll {
+    pub fn down(&mut self, lines: u16) {
+        self.offset = self.offset.saturating_add(lines).min(self.max.get());
+    }
+
+    pub fn up(&mut self, lines: u16) {
+        self.offset = self.offset.saturating_sub(lines);
+    }
+
+    pub fn page_down(&mut self) {
+        self.down(self.page.get().max(1));
+    }
+
+    pub fn page_up(&mut self) {
+        self.up(self.page.get().max(1));
+    }
 }
 
 impl Session {
@@ -62,6 +96,11 @@ impl Session {
         self.graded = None;
         self.input_buffer.clear();
         self.insert_mode = false;
+        self.scroll.offset = 0;
+    }
+
+    pub fn limit_reached(&self) -> bool {
+        self.limit.is_some_and(|limit| self.answered >= limit)
     }
 
     pub fn advance(&mut self) -> bool {
@@ -78,30 +117,81 @@ impl Session {
 }
 
 pub fn start_session(conn: &Connection, session_type: Type) -> Result<(Session, Screen)> {
-    match session_type {
-        Type::Group => group_session(&conn),
-        Type::Marked => marks_session(&conn),
-        Type::Weak => weak_session(&conn),
+    let (mut session, screen) = match session_type {
+        Type::Group => group_session(&conn)?,
+        Type::Marked => marks_session(&conn)?,
+        Type::Weak => weak_session(&conn)?,
         Type::Custom => anyhow::bail!("Custom session requires query input"),
-    }
+    };
+    session.limit = Settings::load(conn)?.session_limit();
+    Ok((session, screen))
 }
 
 pub fn group_session(conn: &Connection) -> Result<(Session, Screen)> {
-    let (screen, group_id, index) = queries::fetch_progress(conn)?;
+    let (screen, group_id, mut index) = queries::fetch_progress(conn)?;
+
+    let mut words = queries::fetch_words_by_group(&conn, group_id)?;
+    if Settings::load(conn)?.shuffle {
+        // Same order every time, so the saved index still points at the
+        // next word
+        words.sort_by_key(|w| group_order(group_id, w.id));
+    }
 
-    let words = queries::fetch_words_by_group(&conn, group_id)?;
+    // Words may have been removed from the group since the position was saved
+    if index >= words.len() {
+        index = 0;
+    }
 
     Ok((Session::new(words, index, Type::Group), screen))
 }
 
+/// Start `group` in `screen` and make it the saved position, resuming where
+/// it left off if it already was.
+pub fn choose_group(conn: &Connection, group_id: i32, screen: Screen) -> Result<Session> {
+    let (_, saved_group, saved_index) = queries::fetch_progress(conn)?;
+    let index = if saved_group == group_id {
+        saved_index
+    } else {
+        0
+    };
+    queries::save_progress(conn, (screen, group_id, index))?;
+
+    let (mut session, _) = group_session(conn)?;
+    session.limit = Settings::load(conn)?.session_limit();
+    Ok(session)
+}
+
 pub fn marks_session(conn: &Connection) -> Result<(Session, Screen)> {
-    let words = queries::fetch_marked_words(&conn)?;
+    let mut words = queries::fetch_marked_words(&conn)?;
+    shuffle(conn, &mut words)?;
 
     Ok((Session::new(words, 0, Type::Marked), Screen::Practice))
 }
 
 pub fn weak_session(conn: &Connection) -> Result<(Session, Screen)> {
-    let words = queries::fetch_weak_words(&conn)?;
+    let mut words = queries::fetch_weak_words(&conn)?;
+    shuffle(conn, &mut words)?;
 
     Ok((Session::new(words, 0, Type::Weak), Screen::Practice))
 }
+
+/// Sort key for a shuffled group: FNV-1a of the group and word ids, which,
+/// unlike the std hashers, gives the same order on every Rust release.
+fn group_order(group_id: i32, word_id: i32) -> u64 {
+    let bytes = group_id
+        .to_le_bytes()
+        .into_iter()
+        .chain(word_id.to_le_bytes());
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Put `words` in random order if shuffling is turned on.
+fn shuffle(conn: &Connection, words: &mut [Word]) -> Result<()> {
+    if Settings::load(conn)?.shuffle {
+        let state = RandomState::new();
+        words.sort_by_key(|w| state.hash_one(w.id));
+    }
+    Ok(())
+}
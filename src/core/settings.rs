@@ -0,0 +1,374 @@
+use crate::config::Config;
+use crate::db::queries;
+use crate::ui::keymap;
+use crate::ui::theme::{self, Theme};
+use anyhow::Result;
+use rusqlite::Connection;
+
+/// Session sizes offered on the settings screen; 0 means no limit.
+pub const SESSION_SIZES: [usize; 5] = [0, 10, 20, 50, 100];
+
+/// How closely a typed answer in Test mode has to match the word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Letter for letter, including case
+    Exact,
+    /// Ignoring case and surrounding spaces
+    Normal,
+    /// Also ignoring spaces and hyphens, and allowing one typo in longer words
+    Lenient,
+}
+
+impl Strictness {
+    const ALL: [Strictness; 3] = [Strictness::Exact, Strictness::Normal, Strictness::Lenient];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Strictness::Exact => "exact",
+            Strictness::Normal => "normal",
+            Strictness::Lenient => "lenient",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.label() == s)
+    }
+
+    pub fn accepts(self, answer: &str, word: &str) -> bool {
+        let answer = answer.trim();
+        match self {
+            Strictness::Exact => answer == word,
+            Strictness::Normal => answer.eq_ignore_ascii_case(word),
+            Strictness::Lenient => {
+                let answer = loose(answer);
+                let word = loose(word);
+                answer == word || (word.chars().count() >= 5 && edit_distance(&answer, &word) <= 1)
+            }
+        }
+    }
+}
+
+/// Lowercased with spaces and hyphens removed.
+fn loose(s: &str) -> String {
+    s.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + (ca != *cb) as usize;
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// One line of the settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Sounds,
+    SessionSize,
+    Strictness,
+    Theme,
+    Shuffle,
+    TutorialAutoAdvance,
+    Keys,
+}
+
+impl Field {
+    pub const ALL: [Field; 7] = [
+        Field::Sounds,
+        Field::SessionSize,
+        Field::Strictness,
+        Field::Theme,
+        Field::Shuffle,
+        Field::TutorialAutoAdvance,
+        Field::Keys,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::Sounds => "Sounds",
+            Field::SessionSize => "Session size",
+            Field::Strictness => "Grading strictness",
+            Field::Theme => "Theme",
+            Field::Shuffle => "Shuffle words",
+            Field::TutorialAutoAdvance => "Tutorial auto-advance",
+            Field::Keys => "Key bindings",
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Field::Sounds => "sounds",
+            Field::SessionSize => "session_size",
+            Field::Strictness => "strictness",
+            Field::Theme => "theme",
+            Field::Shuffle => "shuffle",
+            Field::TutorialAutoAdvance => "tutorial_auto_advance",
+            Field::Keys => "keys",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub sounds: bool,
+    /// Words answered before a session returns to the menu, 0 for no limit
+    pub session_size: usize,
+    pub strictness: Strictness,
+    /// A built-in theme or a `[theme.NAME]` section of the config file
+    pub theme: String,
+    pub shuffle: bool,
+    pub tutorial_auto_advance: bool,
+    /// One of `keymap::PRESETS`
+    pub keys: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            sounds: true,
+            session_size: 0,
+            strictness: Strictness::Normal,
+            theme: theme::BUILT_IN[0].to_string(),
+            shuffle: false,
+            tutorial_auto_advance: true,
+            keys: keymap::PRESETS[0].to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// Stored settings, with defaults for anything missing or unreadable.
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let mut settings = Self::default();
+        for (key, value) in queries::fetch_settings(conn)? {
+            let Some(field) = Field::ALL.into_iter().find(|f| f.key() == key) else {
+                continue;
+            };
+            match field {
+                Field::Sounds => settings.sounds = parse_bool(&value, settings.sounds),
+                Field::SessionSize => {
+                    settings.session_size = value.parse().unwrap_or(settings.session_size)
+                }
+                Field::Strictness => {
+                    settings.strictness = Strictness::parse(&value).unwrap_or(settings.strictness)
+                }
+                Field::Theme => settings.theme = value,
+                Field::Keys => settings.keys = value,
+                Field::Shuffle => settings.shuffle = parse_bool(&value, settings.shuffle),
+                Field::TutorialAutoAdvance => {
+                    settings.tutorial_auto_advance =
+                        parse_bool(&value, settings.tutorial_auto_advance)
+                }
+            }
+        }
+        Ok(settings)
+    }
+
+    pub fn save(&self, conn: &Connection) -> Result<()> {
+        // The saved index points into the group's order, which shuffling
+        // changes, so start the group over
+        if Self::load(conn)?.shuffle != self.shuffle {
+            let (screen, group_id, _) = queries::fetch_progress(conn)?;
+            queries::save_progress(conn, (screen, group_id, 0))?;
+        }
+        for field in Field::ALL {
+            queries::set_setting(conn, field.key(), &self.stored(field))?;
+        }
+        Ok(())
+    }
+
+    /// Maximum words per session, `None` for no limit.
+    pub fn session_limit(&self) -> Option<usize> {
+        (self.session_size > 0).then_some(self.session_size)
+    }
+
+    /// The value of `field` as shown on the settings screen.
+    pub fn display(&self, field: Field) -> String {
+        let on_off = |on: bool| if on { "On" } else { "Off" }.to_string();
+        match field {
+            Field::Sounds => on_off(self.sounds),
+            Field::SessionSize => match self.session_limit() {
+                Some(size) => format!("{size} words"),
+                None => "Whole group".into(),
+            },
+            Field::Strictness => self.strictness.label().into(),
+            Field::Theme => self.theme.clone(),
+            Field::Shuffle => on_off(self.shuffle),
+            Field::TutorialAutoAdvance => on_off(self.tutorial_auto_advance),
+            Field::Keys => self.keys.clone(),
+        }
+    }
+
+    /// Step `field` to its next value, or the previous one when `forward`
+    /// is false. Themes include the `[theme.NAME]` sections of `config`.
+    pub fn cycle(&mut self, field: Field, forward: bool, config: &Config) {
+        match field {
+            Field::Sounds => self.sounds = !self.sounds,
+            Field::SessionSize => {
+                self.session_size = step(&SESSION_SIZES, &self.session_size, forward)
+            }
+            Field::Strictness => {
+                self.strictness = step(&Strictness::ALL, &self.strictness, forward)
+            }
+            Field::Theme => {
+                self.theme = step(&Theme::names(config), &self.theme.as_str(), forward).to_string();
+            }
+            Field::Shuffle => self.shuffle = !self.shuffle,
+            Field::TutorialAutoAdvance => self.tutorial_auto_advance = !self.tutorial_auto_advance,
+            Field::Keys => {
+                self.keys = step(&keymap::PRESETS, &self.keys.as_str(), forward).to_string();
+            }
+        }
+    }
+
+    fn stored(&self, field: Field) -> String {
+        match field {
+            Field::Sounds => self.sounds.to_string(),
+            Field::SessionSize => self.session_size.to_string(),
+            Field::Strictness => self.strictness.label().into(),
+            Field::Theme => self.theme.clone(),
+            Field::Shuffle => self.shuffle.to_string(),
+            Field::TutorialAutoAdvance => self.tutorial_auto_advance.to_string(),
+            Field::Keys => self.keys.clone(),
+        }
+    }
+}
+
+fn parse_bool(value: &str, default: bool) -> bool {
+    value.parse().unwrap_or(default)
+}
+
+/// The option after (or before) `current`, wrapping around; the first option
+/// if `current` is not one of them.
+fn step<T: Copy + PartialEq>(options: &[T], current: &T, forward: bool) -> T {
+    let next = match options.iter().position(|o| o == current) {
+        Some(i) if forward => (i + 1) % options.len(),
+        Some(i) => (i + options.len() - 1) % options.len(),
+        None => 0,
+    };
+    options[next]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::session;
+    use crate::db::init_db;
+    use crate::ui::app::Screen;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let conn = init_db(":memory:").unwrap();
+        assert_eq!(Settings::load(&conn).unwrap(), Settings::default());
+
+        let mut settings = Settings::default();
+        settings.cycle(Field::Sounds, true, &Config::default());
+        settings.cycle(Field::SessionSize, true, &Config::default());
+        settings.cycle(Field::Strictness, false, &Config::default());
+        settings.save(&conn).unwrap();
+
+        let loaded = Settings::load(&conn).unwrap();
+        assert_eq!(loaded, settings);
+        assert!(!loaded.sounds);
+        assert_eq!(loaded.session_limit(), Some(10));
+        assert_eq!(loaded.strictness, Strictness::Exact);
+    }
+
+    #[test]
+    fn test_theme_cycles_through_config_themes() {
+        let config = Config::parse("test.conf", "[theme.solar]\ncorrect = yellow\n").unwrap();
+        let mut settings = Settings::default();
+
+        settings.cycle(Field::Theme, false, &config);
+        assert_eq!(settings.theme, "solar");
+        settings.cycle(Field::Theme, true, &config);
+        assert_eq!(settings.theme, "default");
+        settings.cycle(Field::Theme, false, &Config::default());
+        assert_eq!(settings.theme, "monochrome");
+    }
+
+    #[test]
+    fn test_bad_values_fall_back_to_defaults() {
+        let conn = init_db(":memory:").unwrap();
+        queries::set_setting(&conn, "session_size", "lots").unwrap();
+        queries::set_setting(&conn, "shuffle", "true").unwrap();
+        queries::set_setting(&conn, "unknown", "x").unwrap();
+
+        let settings = Settings::load(&conn).unwrap();
+
+        assert_eq!(settings.session_size, 0);
+        assert!(settings.shuffle);
+    }
+
+    #[test]
+    fn test_sessions_use_settings() {
+        let conn = init_db(":memory:").unwrap();
+        for word in ["abound", "austere", "belie", "cursory"] {
+            queries::insert_word(&conn, word, "", 1, "").unwrap();
+        }
+        let settings = Settings {
+            session_size: 20,
+            shuffle: true,
+            ..Settings::default()
+        };
+        settings.save(&conn).unwrap();
+
+        let (first, _) = session::start_session(&conn, session::Type::Group).unwrap();
+        let (second, _) = session::start_session(&conn, session::Type::Group).unwrap();
+
+        assert_eq!(first.limit, Some(20));
+        // Group order is shuffled the same way every time
+        let order = |s: &session::Session| s.words.iter().map(|w| w.id).collect::<Vec<_>>();
+        assert_eq!(order(&first), order(&second));
+        assert_eq!(first.words.len(), 4);
+        // ...and by a hash that doesn't change between Rust releases
+        assert_eq!(order(&first), [4, 1, 3, 2]);
+    }
+
+    #[test]
+    fn test_toggling_shuffle_restarts_the_group() {
+        let conn = init_db(":memory:").unwrap();
+        for word in ["abound", "austere", "belie", "cursory"] {
+            queries::insert_word(&conn, word, "", 2, "").unwrap();
+        }
+        queries::save_progress(&conn, (Screen::Test, 2, 3)).unwrap();
+
+        let mut settings = Settings::load(&conn).unwrap();
+        settings.cycle(Field::Sounds, true, &Config::default());
+        settings.save(&conn).unwrap();
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Test, 2, 3)
+        );
+
+        settings.cycle(Field::Shuffle, true, &Config::default());
+        settings.save(&conn).unwrap();
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Test, 2, 0)
+        );
+    }
+
+    #[test]
+    fn test_strictness() {
+        assert!(Strictness::Exact.accepts(" abound ", "abound"));
+        assert!(!Strictness::Exact.accepts("Abound", "abound"));
+        assert!(Strictness::Normal.accepts("ABOUND", "abound"));
+        assert!(!Strictness::Normal.accepts("abund", "abound"));
+        assert!(Strictness::Lenient.accepts("abund", "abound"));
+        assert!(Strictness::Lenient.accepts("adhoc", "ad hoc"));
+        assert!(!Strictness::Lenient.accepts("abd", "abound"));
+        assert!(!Strictness::Lenient.accepts("bel", "belie"));
+    }
+}
@@ -0,0 +1,401 @@
+use crate::core::heatmap::Heatmap;
+use crate::core::utils;
+use crate::db::{models::Word, queries};
+use anyhow::Result;
+use chrono::Local;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// How many of the weakest words a report lists.
+pub const WEAKEST_COUNT: usize = 10;
+
+/// Days of activity shown on the statistics screen.
+pub const ACTIVITY_DAYS: usize = 30;
+
+/// A word counts as mastered once seen this often...
+const MASTERED_MIN_SEEN: u8 = 3;
+/// ...with at least this accuracy.
+const MASTERED_ACCURACY: f64 = 0.8;
+
+/// Review totals for a set of words.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Totals {
+    pub words: usize,
+    pub seen: usize,
+    pub unseen: usize,
+    pub marked: usize,
+    pub reviews: u32,
+    pub correct: u32,
+    /// `correct / reviews`, `None` until something has been reviewed
+    pub accuracy: Option<f64>,
+}
+
+impl Totals {
+    fn add(&mut self, word: &Word) {
+        self.words += 1;
+        if word.times_seen > 0 {
+            self.seen += 1;
+        } else {
+            self.unseen += 1;
+        }
+        self.marked += word.marked as usize;
+        self.reviews += word.times_seen as u32;
+        self.correct += word.success_count as u32;
+        self.accuracy = (self.reviews > 0).then(|| self.correct as f64 / self.reviews as f64);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupStats {
+    pub group: i32,
+    #[serde(flatten)]
+    pub totals: Totals,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeakWord {
+    pub word: String,
+    pub group: i32,
+    pub times_seen: u8,
+    pub success_count: u8,
+    pub accuracy: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    #[serde(flatten)]
+    pub totals: Totals,
+    pub groups: Vec<GroupStats>,
+    pub weakest: Vec<WeakWord>,
+    /// Unix timestamp of the most recent review
+    pub last_activity: Option<i32>,
+}
+
+impl Report {
+    /// Stats over every word, or only over `group`.
+    pub fn compute(conn: &Connection, group: Option<i32>) -> Result<Self> {
+        let words: Vec<Word> = queries::fetch_all_words(conn)?
+            .into_iter()
+            .filter(|w| group.is_none_or(|g| w.group_id == g))
+            .collect();
+
+        let mut totals = Totals::default();
+        let mut groups: BTreeMap<i32, Totals> = BTreeMap::new();
+        for word in &words {
+            totals.add(word);
+            groups.entry(word.group_id).or_default().add(word);
+        }
+
+        Ok(Self {
+            totals,
+            groups: groups
+                .into_iter()
+                .map(|(group, totals)| GroupStats { group, totals })
+                .collect(),
+            weakest: weakest(&words, WEAKEST_COUNT),
+            last_activity: words.iter().filter_map(|w| w.last_seen).max(),
+        })
+    }
+
+    pub fn write_text<W: Write>(&self, out: &mut W) -> Result<()> {
+        let t = &self.totals;
+        writeln!(out, "Words:         {}", t.words)?;
+        writeln!(out, "Seen:          {} ({} unseen)", t.seen, t.unseen)?;
+        writeln!(out, "Marked:        {}", t.marked)?;
+        writeln!(
+            out,
+            "Accuracy:      {} ({}/{} reviews)",
+            percent(t.accuracy),
+            t.correct,
+            t.reviews
+        )?;
+        writeln!(
+            out,
+            "Last activity: {}",
+            utils::relative_time(self.last_activity)
+        )?;
+
+        if !self.groups.is_empty() {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "{:<8} {:>6} {:>6} {:>7} {:>9}",
+                "Group", "Words", "Seen", "Marked", "Accuracy"
+            )?;
+            for g in &self.groups {
+                writeln!(
+                    out,
+                    "{:<8} {:>6} {:>6} {:>7} {:>9}",
+                    g.group,
+                    g.totals.words,
+                    g.totals.seen,
+                    g.totals.marked,
+                    percent(g.totals.accuracy)
+                )?;
+            }
+        }
+
+        if !self.weakest.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "Weakest words:")?;
+            for w in &self.weakest {
+                writeln!(
+                    out,
+                    "  {:<20} {}/{} (Group {})",
+                    w.word, w.success_count, w.times_seen, w.group
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How far along the words are.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Mastery {
+    pub mastered: usize,
+    pub learning: usize,
+    pub unseen: usize,
+}
+
+impl Mastery {
+    pub fn compute(words: &[Word]) -> Self {
+        let mut mastery = Self::default();
+        for word in words {
+            if word.times_seen == 0 {
+                mastery.unseen += 1;
+            } else if word.times_seen >= MASTERED_MIN_SEEN
+                && word.success_count as f64 / word.times_seen as f64 >= MASTERED_ACCURACY
+            {
+                mastery.mastered += 1;
+            } else {
+                mastery.learning += 1;
+            }
+        }
+        mastery
+    }
+
+    pub fn total(&self) -> usize {
+        self.mastered + self.learning + self.unseen
+    }
+}
+
+/// Data behind the statistics screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dashboard {
+    pub groups: Vec<GroupStats>,
+    pub mastery: Mastery,
+    /// Reviews on each of the past `ACTIVITY_DAYS` days, oldest first
+    pub activity: Vec<u64>,
+    pub heatmap: Heatmap,
+    pub weakest: Vec<WeakWord>,
+    /// Whether the screen shows the year heatmap instead of the charts
+    pub show_heatmap: bool,
+}
+
+impl Dashboard {
+    pub fn compute(conn: &Connection) -> Result<Self> {
+        let words = queries::fetch_all_words(conn)?;
+        let report = Report::compute(conn, None)?;
+
+        let heatmap = Heatmap::compute(conn, Local::now().date_naive())?;
+
+        Ok(Self {
+            groups: report.groups,
+            mastery: Mastery::compute(&words),
+            activity: heatmap
+                .last_days(ACTIVITY_DAYS)
+                .iter()
+                .map(|c| *c as u64)
+                .collect(),
+            heatmap,
+            weakest: weakest(&queries::fetch_weak_words(conn)?, WEAKEST_COUNT),
+            show_heatmap: false,
+        })
+    }
+}
+
+/// One row of the group picker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupSummary {
+    pub group: i32,
+    pub totals: Totals,
+    pub mastery: Mastery,
+}
+
+impl GroupSummary {
+    /// Share of the group's words seen at least once.
+    pub fn seen_ratio(&self) -> f64 {
+        self.totals.seen as f64 / self.totals.words.max(1) as f64
+    }
+}
+
+/// Every group with its totals and mastery, in group order.
+pub fn group_summaries(conn: &Connection) -> Result<Vec<GroupSummary>> {
+    let mut groups: BTreeMap<i32, Vec<Word>> = BTreeMap::new();
+    for word in queries::fetch_all_words(conn)? {
+        groups.entry(word.group_id).or_default().push(word);
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(group, words)| {
+            let mut totals = Totals::default();
+            words.iter().for_each(|w| totals.add(w));
+            GroupSummary {
+                group,
+                totals,
+                mastery: Mastery::compute(&words),
+            }
+        })
+        .collect())
+}
+
+/// Seen words with the lowest accuracy, the most-reviewed first on ties.
+pub fn weakest(words: &[Word], count: usize) -> Vec<WeakWord> {
+    let mut seen: Vec<WeakWord> = words
+        .iter()
+        .filter(|w| w.times_seen > 0)
+        .map(|w| WeakWord {
+            word: w.word.clone(),
+            group: w.group_id,
+            times_seen: w.times_seen,
+            success_count: w.success_count,
+            accuracy: w.success_count as f64 / w.times_seen as f64,
+        })
+        .collect();
+
+    seen.sort_by(|a, b| {
+        a.accuracy
+            .total_cmp(&b.accuracy)
+            .then(b.times_seen.cmp(&a.times_seen))
+    });
+    seen.truncate(count);
+    seen
+}
+
+pub fn percent(ratio: Option<f64>) -> String {
+    match ratio {
+        Some(r) => format!("{:.0}%", r * 100.0),
+        None => "-".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn setup() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        for (word, group, seen, correct) in [
+            ("abound", 1, 4, 3),
+            ("austere", 1, 2, 0),
+            ("belie", 2, 0, 0),
+            ("cursory", 2, 5, 1),
+        ] {
+            queries::insert_word(&conn, word, "", group, "").unwrap();
+            queries::set_review_counts(&conn, word, seen, correct).unwrap();
+        }
+        conn.execute(
+            "UPDATE words SET marked=1, last_seen=100 WHERE word='belie'",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_totals_and_groups() {
+        let report = Report::compute(&setup(), None).unwrap();
+
+        assert_eq!(report.totals.words, 4);
+        assert_eq!((report.totals.seen, report.totals.unseen), (3, 1));
+        assert_eq!(report.totals.marked, 1);
+        assert_eq!((report.totals.correct, report.totals.reviews), (4, 11));
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.groups[0].totals.accuracy, Some(0.5));
+        assert_eq!(report.last_activity, Some(100));
+    }
+
+    #[test]
+    fn test_weakest_order() {
+        let report = Report::compute(&setup(), None).unwrap();
+        let words: Vec<&str> = report.weakest.iter().map(|w| w.word.as_str()).collect();
+
+        assert_eq!(words, vec!["austere", "cursory", "abound"]);
+    }
+
+    #[test]
+    fn test_single_group() {
+        let report = Report::compute(&setup(), Some(2)).unwrap();
+
+        assert_eq!(report.totals.words, 2);
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(percent(report.totals.accuracy), "20%");
+    }
+
+    #[test]
+    fn test_mastery_levels() {
+        let conn = setup();
+        queries::set_review_counts(&conn, "abound", 5, 4).unwrap();
+
+        let mastery = Mastery::compute(&queries::fetch_all_words(&conn).unwrap());
+
+        assert_eq!(
+            mastery,
+            Mastery {
+                mastered: 1,
+                learning: 2,
+                unseen: 1
+            }
+        );
+        assert_eq!(mastery.total(), 4);
+    }
+
+    #[test]
+    fn test_dashboard_activity_comes_from_reviews() {
+        let conn = setup();
+        let now = Local::now().timestamp();
+        queries::insert_review(&conn, 1, now, true).unwrap();
+        queries::insert_review(&conn, 2, now, false).unwrap();
+
+        let dashboard = Dashboard::compute(&conn).unwrap();
+
+        assert_eq!(dashboard.activity.len(), ACTIVITY_DAYS);
+        assert_eq!(dashboard.activity.last(), Some(&2));
+        assert_eq!(dashboard.heatmap.total(), 2);
+    }
+
+    #[test]
+    fn test_group_summaries() {
+        let conn = setup();
+
+        let groups = group_summaries(&conn).unwrap();
+
+        assert_eq!(groups.iter().map(|g| g.group).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(groups[1].totals.words, 2);
+        assert_eq!(groups[1].seen_ratio(), 0.5);
+        assert_eq!(
+            groups[1].mastery,
+            Mastery {
+                mastered: 0,
+                learning: 1,
+                unseen: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_json_is_flat() {
+        let report = Report::compute(&setup(), None).unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["words"], 4);
+        assert_eq!(json["groups"][1]["group"], 2);
+        assert_eq!(json["groups"][1]["seen"], 1);
+    }
+}
@@ -2,10 +2,13 @@
 // Manages tutorial state, step progression, and validation logic
 
 use crate::core::session::Session;
-use crate::ui::app::App;
-use crossterm::event::KeyCode;
+use crate::ui::app::{App, Screen};
+use crate::ui::keymap::{Action, Keymap, key_name};
 
 /// Defines a single step in the tutorial sequence
+///
+/// `instruction` and `hint` name keys by action, e.g. `{show}`, so they match
+/// the key bindings in use; see `TutorialStep::instruction`.
 pub struct TutorialStep {
     pub id: usize,
     pub instruction: &'static str,
@@ -14,10 +17,41 @@ pub struct TutorialStep {
     pub highlight: Option<HighlightTarget>,
 }
 
+impl TutorialStep {
+    /// The instruction, with keys from `keymap`
+    pub fn instruction_text(&self, keymap: &Keymap) -> String {
+        fill_keys(self.instruction, keymap)
+    }
+
+    /// The hint, with keys from `keymap`
+    pub fn hint_text(&self, keymap: &Keymap) -> String {
+        fill_keys(self.hint.unwrap_or("Try again."), keymap)
+    }
+}
+
+/// Replace each `{action}` in `text` with the tutorial keys for that action,
+/// e.g. `{back}` becomes "'q' or Esc"
+pub fn fill_keys(text: &str, keymap: &Keymap) -> String {
+    let mut text = text.to_string();
+    for binding in keymap.bindings(Screen::Tutorial) {
+        let keys = binding
+            .keys
+            .iter()
+            .map(|key| match key_name(*key) {
+                name if name.chars().count() == 1 => format!("'{name}'"),
+                name => name,
+            })
+            .collect::<Vec<_>>()
+            .join(" or ");
+        text = text.replace(&format!("{{{}}}", binding.action.name()), &keys);
+    }
+    text
+}
+
 /// Validation criteria for completing a tutorial step
 pub enum StepValidation {
-    /// Step completes when a specific key is pressed
-    KeyPress(KeyCode),
+    /// Step completes when a key bound to one of the actions is pressed
+    KeyPress(&'static [Action]),
     /// Step completes when a specific menu item is selected
     MenuSelection(usize),
     /// Step completes when a custom condition function returns true
@@ -28,8 +62,8 @@ pub enum StepValidation {
 pub enum HighlightTarget {
     /// Highlight a specific menu option by index
     MenuOption(usize),
-    /// Highlight a keyboard shortcut hint
-    KeyHint(&'static str),
+    /// Highlight the buttons for these actions
+    KeyHint(&'static [Action]),
 }
 
 /// Represents the current state of the tutorial
@@ -58,35 +92,35 @@ pub const TUTORIAL_STEPS: &[TutorialStep] = &[
     // Step 0: Welcome
     TutorialStep {
         id: 0,
-        instruction: "Welcome to Vocabulator! This tutorial will teach you how to use the app. Press Enter to continue.",
-        hint: Some("Press the Enter key to proceed."),
-        validation: StepValidation::KeyPress(KeyCode::Enter),
+        instruction: "Welcome to Vocabulator! This tutorial will teach you how to use the app. Press {next} to continue.",
+        hint: Some("Press {next} to proceed."),
+        validation: StepValidation::KeyPress(&[Action::Next]),
         highlight: None,
     },
     
     // Step 1: Menu navigation down
     TutorialStep {
         id: 1,
-        instruction: "Use the Down arrow or 'j' key to move down in the menu. Try it now.",
-        hint: Some("Press Down arrow or 'j' to move the selection down."),
-        validation: StepValidation::KeyPress(KeyCode::Down),
+        instruction: "Use {down} to move down in the menu. Try it now.",
+        hint: Some("Press {down} to move the selection down."),
+        validation: StepValidation::KeyPress(&[Action::Down]),
         highlight: Some(HighlightTarget::MenuOption(1)),
     },
     
     // Step 2: Menu navigation up
     TutorialStep {
         id: 2,
-        instruction: "Use the Up arrow or 'k' key to move up. Try moving back up.",
-        hint: Some("Press Up arrow or 'k' to move the selection up."),
-        validation: StepValidation::KeyPress(KeyCode::Up),
+        instruction: "Use {up} to move up. Try moving back up.",
+        hint: Some("Press {up} to move the selection up."),
+        validation: StepValidation::KeyPress(&[Action::Up]),
         highlight: Some(HighlightTarget::MenuOption(0)),
     },
     
     // Step 3: Select Continue Learning
     TutorialStep {
         id: 3,
-        instruction: "Press Enter to select 'Continue Learning' and start a practice session.",
-        hint: Some("Make sure 'Continue Learning' is highlighted, then press Enter."),
+        instruction: "Press {next} to select 'Continue Learning' and start a practice session.",
+        hint: Some("Make sure 'Continue Learning' is highlighted, then press {next}."),
         validation: StepValidation::MenuSelection(0),
         highlight: Some(HighlightTarget::MenuOption(0)),
     },
@@ -106,31 +140,26 @@ pub const TUTORIAL_STEPS: &[TutorialStep] = &[
     // Step 5: Show definition
     TutorialStep {
         id: 5,
-        instruction: "Press 's' to show the definition.",
-        hint: Some("Press the 's' key to reveal the definition."),
-        validation: StepValidation::KeyPress(KeyCode::Char('s')),
-        highlight: Some(HighlightTarget::KeyHint("s")),
+        instruction: "Press {show} to show the definition.",
+        hint: Some("Press {show} to reveal the definition."),
+        validation: StepValidation::KeyPress(&[Action::Show]),
+        highlight: Some(HighlightTarget::KeyHint(&[Action::Show])),
     },
     
-    // Step 6: Grade (accept both y and n)
+    // Step 6: Grade (accept both correct and wrong)
     TutorialStep {
         id: 6,
-        instruction: "Grade yourself honestly. Press 'y' if you knew it, or 'n' if you didn't.",
-        hint: Some("Press 'y' for correct or 'n' for incorrect."),
-        validation: StepValidation::StateCondition(|_app, _state| {
-            // This step is validated by the key press handler
-            // We'll check if the user pressed y or n in the validation logic
-            // For now, this will be handled by the special case in validate_and_advance
-            true // This will be overridden by the key check
-        }),
-        highlight: Some(HighlightTarget::KeyHint("y/n")),
+        instruction: "Grade yourself honestly. Press {correct} if you knew it, or {wrong} if you didn't.",
+        hint: Some("Press {correct} for correct or {wrong} for incorrect."),
+        validation: StepValidation::KeyPress(&[Action::Correct, Action::Wrong]),
+        highlight: Some(HighlightTarget::KeyHint(&[Action::Correct, Action::Wrong])),
     },
     
     // Step 7: Bookmark feature
     TutorialStep {
         id: 7,
-        instruction: "Press 'm' to bookmark this word. Bookmarked words show a star (*).",
-        hint: Some("Press the 'm' key to toggle the bookmark."),
+        instruction: "Press {mark} to bookmark this word. Bookmarked words show a star (*).",
+        hint: Some("Press {mark} to toggle the bookmark."),
         validation: StepValidation::StateCondition(|_app, state| {
             // Check if word is marked in the tutorial sample session
             let session = match &state.sample_session {
@@ -144,14 +173,14 @@ pub const TUTORIAL_STEPS: &[TutorialStep] = &[
                 false
             }
         }),
-        highlight: Some(HighlightTarget::KeyHint("m")),
+        highlight: Some(HighlightTarget::KeyHint(&[Action::Mark])),
     },
     
     // Step 8: Unbookmark
     TutorialStep {
         id: 8,
-        instruction: "Press 'm' again to remove the bookmark.",
-        hint: Some("Press the 'm' key to toggle the bookmark off."),
+        instruction: "Press {mark} again to remove the bookmark.",
+        hint: Some("Press {mark} to toggle the bookmark off."),
         validation: StepValidation::StateCondition(|_app, state| {
             // Check if word is unmarked
             let session = match &state.sample_session {
@@ -165,23 +194,23 @@ pub const TUTORIAL_STEPS: &[TutorialStep] = &[
                 false
             }
         }),
-        highlight: Some(HighlightTarget::KeyHint("m")),
+        highlight: Some(HighlightTarget::KeyHint(&[Action::Mark])),
     },
     
     // Step 9: Explain Review Marks feature
     TutorialStep {
         id: 9,
-        instruction: "Bookmarked words can be reviewed later! Use 'Review Marks' from the main menu to practice only your bookmarked words. Press Enter to continue.",
-        hint: Some("Press Enter to continue learning about the app."),
-        validation: StepValidation::KeyPress(KeyCode::Enter),
+        instruction: "Bookmarked words can be reviewed later! Use 'Review Marks' from the main menu to practice only your bookmarked words. Press {next} to continue.",
+        hint: Some("Press {next} to continue learning about the app."),
+        validation: StepValidation::KeyPress(&[Action::Next]),
         highlight: None,
     },
     
     // Step 10: Advance to next word
     TutorialStep {
         id: 10,
-        instruction: "Press Enter to move to the next word.",
-        hint: Some("Press the Enter key to advance to the next word."),
+        instruction: "Press {next} to move to the next word.",
+        hint: Some("Press {next} to advance to the next word."),
         validation: StepValidation::StateCondition(|_app, state| {
             // Check if we've advanced to the next word
             let session = match &state.sample_session {
@@ -191,13 +220,13 @@ pub const TUTORIAL_STEPS: &[TutorialStep] = &[
             // We should have moved to index 1 or higher
             session.index >= 1
         }),
-        highlight: Some(HighlightTarget::KeyHint("Enter")),
+        highlight: Some(HighlightTarget::KeyHint(&[Action::Next])),
     },
     
     // Step 11: Practice more words
     TutorialStep {
         id: 11,
-        instruction: "Practice with a few more words using 's', 'y'/'n', 'm', and Enter as you like.",
+        instruction: "Practice with a few more words using {show}, {correct}/{wrong}, {mark}, and {next} as you like.",
         hint: Some("Use the practice controls freely. Advance to at least 2 more words to continue."),
         validation: StepValidation::StateCondition(|_app, state| {
             // Check if we've advanced to word index 2 or higher in the tutorial sample session
@@ -213,18 +242,18 @@ pub const TUTORIAL_STEPS: &[TutorialStep] = &[
     // Step 12: Exit to menu
     TutorialStep {
         id: 12,
-        instruction: "Press 'q' or Escape to return to the main menu.",
-        hint: Some("Press 'q' or Escape to exit the practice session."),
-        validation: StepValidation::KeyPress(KeyCode::Char('q')),
-        highlight: Some(HighlightTarget::KeyHint("q")),
+        instruction: "Press {back} to return to the main menu.",
+        hint: Some("Press {back} to exit the practice session."),
+        validation: StepValidation::KeyPress(&[Action::Back]),
+        highlight: Some(HighlightTarget::KeyHint(&[Action::Back])),
     },
     
     // Step 13: Completion
     TutorialStep {
         id: 13,
-        instruction: "Great job! You've learned the basics. There's also a Test mode where you type the word from the definition. Your progress auto-saves. Press Enter to finish.",
-        hint: Some("Press Enter to complete the tutorial."),
-        validation: StepValidation::KeyPress(KeyCode::Enter),
+        instruction: "Great job! You've learned the basics. There's also a Test mode where you type the word from the definition. Your progress auto-saves. Press {next} to finish.",
+        hint: Some("Press {next} to complete the tutorial."),
+        validation: StepValidation::KeyPress(&[Action::Next]),
         highlight: None,
     },
 ];
@@ -299,6 +328,8 @@ pub fn create_sample_session() -> Session {
             last_seen: None, // Never seen before
             times_seen: 0, // Default statistics
             success_count: 0, // Default statistics
+            tags: String::new(),
+            example: None,
         })
         .collect();
 
@@ -310,6 +341,7 @@ pub fn create_sample_session() -> Session {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crossterm::event::KeyCode;
 
     #[test]
     fn test_init_tutorial_creates_valid_state() {
@@ -526,6 +558,27 @@ mod tests {
         assert_eq!(state.current_step, 7);
     }
 
+    #[test]
+    fn test_step_text_uses_key_bindings() {
+        use crate::ui::keymap::Keymap;
+
+        let keymap = Keymap::default();
+        assert_eq!(
+            TUTORIAL_STEPS[1].instruction_text(&keymap),
+            "Use Down or 'j' to move down in the menu. Try it now."
+        );
+        assert_eq!(
+            TUTORIAL_STEPS[12].hint_text(&keymap),
+            "Press 'q' or Esc to exit the practice session."
+        );
+
+        let keymap = Keymap::preset("arrows-only").unwrap();
+        assert_eq!(
+            TUTORIAL_STEPS[6].instruction_text(&keymap),
+            "Grade yourself honestly. Press Right if you knew it, or Left if you didn't."
+        );
+    }
+
     #[test]
     fn test_validate_and_advance_returns_complete_at_end() {
         use crossterm::event::{KeyEvent, KeyModifiers};
@@ -657,6 +710,8 @@ mod tests {
             last_seen: None,
             times_seen: 0,
             success_count: 0,
+            tags: String::new(),
+            example: None,
         }];
         app.session = Some(Session::new(words, 0, crate::core::session::Type::Group));
 
@@ -850,7 +905,7 @@ pub fn get_current_step(state: &TutorialState) -> &TutorialStep {
 ///
 /// Checks if the provided key event satisfies the validation criteria for the current step.
 /// Supports:
-/// - KeyPress validation with alternatives (j/k for arrow keys)
+/// - KeyPress validation against the tutorial key bindings
 /// - MenuSelection validation (checks selected menu index)
 /// - StateCondition validation (evaluates custom condition function)
 ///
@@ -872,35 +927,14 @@ pub fn validate_and_advance(
 
     let current_step = get_current_step(state);
     let is_valid = match &current_step.validation {
-        StepValidation::KeyPress(expected_key) => {
-            // Check for exact key match
-            if key.code == *expected_key {
-                true
-            } else {
-                // Check for key alternatives (j/k for arrows, Escape for q)
-                match (*expected_key, key.code) {
-                    // Down arrow alternatives
-                    (KeyCode::Down, KeyCode::Char('j')) => true,
-                    // Up arrow alternatives
-                    (KeyCode::Up, KeyCode::Char('k')) => true,
-                    // 'q' alternatives
-                    (KeyCode::Char('q'), KeyCode::Esc) => true,
-                    // 'y' or 'n' for grading step (step 6)
-                    (KeyCode::Char('y'), KeyCode::Char('n')) if current_step.id == 6 => true,
-                    (KeyCode::Char('n'), KeyCode::Char('y')) if current_step.id == 6 => true,
-                    _ => false,
-                }
-            }
-        }
+        StepValidation::KeyPress(actions) => actions
+            .iter()
+            .any(|action| app.keymap.matches(Screen::Tutorial, *action, &key)),
         StepValidation::MenuSelection(expected_index) => {
-            // For menu selection, we need to check if Enter was pressed
-            // and the menu is at the correct index
-            if key.code == KeyCode::Enter {
-                // Check if the menu selection matches the expected index
-                app.selected == *expected_index
-            } else {
-                false
-            }
+            // The selection is confirmed with the key for the next step,
+            // and the menu must be at the correct index
+            app.keymap.matches(Screen::Tutorial, Action::Next, &key)
+                && app.selected == *expected_index
         }
         StepValidation::StateCondition(condition_fn) => {
             // Evaluate the custom condition function
@@ -923,8 +957,7 @@ pub fn validate_and_advance(
         }
     } else {
         // Return hint message for invalid action
-        let hint = current_step.hint.unwrap_or("Try again.");
-        ValidationResult::Invalid(hint.to_string())
+        ValidationResult::Invalid(current_step.hint_text(&app.keymap))
     }
 }
 
@@ -0,0 +1,177 @@
+use super::schema::MIGRATIONS;
+use anyhow::{Result, bail};
+use chrono::Local;
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory next to the database holding automatic snapshots.
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// How many automatic snapshots are kept; older ones are deleted.
+pub const SNAPSHOT_LIMIT: usize = 10;
+
+/// Copy the open database to `dest` with SQLite's online backup API, so it
+/// is consistent even while the app is running.
+pub fn backup(conn: &Connection, dest: &Path) -> Result<()> {
+    if dest.exists() {
+        bail!("{} already exists", dest.display());
+    }
+    if let Some(parent) = dest.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    conn.backup(DatabaseName::Main, dest, None)?;
+    Ok(())
+}
+
+/// Replace the contents of the open database with the backup at `src`,
+/// snapshotting the current contents first. Returns the snapshot's path.
+pub fn restore(conn: &mut Connection, src: &Path) -> Result<Option<PathBuf>> {
+    check_backup(src)?;
+    let saved = snapshot(conn, "restore")?;
+    conn.restore(
+        DatabaseName::Main,
+        src,
+        None::<fn(rusqlite::backup::Progress)>,
+    )?;
+    Ok(saved)
+}
+
+/// Make sure `src` is a database this version can read before restoring.
+fn check_backup(src: &Path) -> Result<()> {
+    if !src.is_file() {
+        bail!("{} does not exist", src.display());
+    }
+    let conn = Connection::open_with_flags(src, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let has_words = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='words'",
+            [],
+            |row| row.get::<_, i32>(0),
+        )
+        .map_err(|_| anyhow::anyhow!("{} is not a SQLite database", src.display()))?;
+    if has_words == 0 {
+        bail!("{} is not a vocabulator database", src.display());
+    }
+
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        bail!(
+            "{} was made by a newer version of vocabulator",
+            src.display()
+        );
+    }
+    Ok(())
+}
+
+/// A timestamped backup file name, e.g. `vocab-20240131-093000.db`.
+pub fn default_name() -> String {
+    format!("vocab-{}.db", Local::now().format("%Y%m%d-%H%M%S"))
+}
+
+/// Save a copy of the database in the snapshot directory before a risky
+/// change, keeping only the newest `SNAPSHOT_LIMIT`. Nothing is saved for
+/// in-memory or empty databases. Returns the snapshot's path.
+pub fn snapshot(conn: &Connection, reason: &str) -> Result<Option<PathBuf>> {
+    let Some(path) = conn.path().filter(|p| !p.is_empty()) else {
+        return Ok(None);
+    };
+    let words: i64 = conn.query_row("SELECT COUNT(*) FROM words", [], |row| row.get(0))?;
+    if words == 0 {
+        return Ok(None);
+    }
+
+    let dir = Path::new(path)
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(SNAPSHOT_DIR);
+    let dest = dir.join(format!(
+        "vocab-{}-{reason}.db",
+        Local::now().format("%Y%m%d-%H%M%S%.3f")
+    ));
+    backup(conn, &dest)?;
+    rotate(&dir, SNAPSHOT_LIMIT)?;
+    Ok(Some(dest))
+}
+
+/// Delete the oldest snapshots so at most `keep` remain. Names start with
+/// the time they were taken, so name order is age order.
+fn rotate(dir: &Path, keep: usize) -> Result<()> {
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("vocab-") && n.ends_with(".db"))
+        })
+        .collect();
+    snapshots.sort();
+
+    let excess = snapshots.len().saturating_sub(keep);
+    for old in &snapshots[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{init_db, queries};
+
+    #[test]
+    fn test_backup_and_restore_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = init_db(dir.path().join("vocab.db").to_str().unwrap()).unwrap();
+        queries::insert_word(&conn, "abound", "be plentiful", 1, "").unwrap();
+
+        let copy = dir.path().join("copy.db");
+        backup(&conn, &copy).unwrap();
+        assert!(backup(&conn, &copy).is_err());
+
+        let mut other = init_db(":memory:").unwrap();
+        assert_eq!(restore(&mut other, &copy).unwrap(), None);
+
+        assert!(queries::fetch_word(&other, "abound").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_restore_rejects_foreign_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("notes.txt");
+        fs::write(&text, "not a database").unwrap();
+        let empty = dir.path().join("empty.db");
+        Connection::open(&empty)
+            .unwrap()
+            .execute_batch("CREATE TABLE t (x)")
+            .unwrap();
+
+        let mut conn = init_db(":memory:").unwrap();
+
+        assert!(restore(&mut conn, &text).is_err());
+        assert!(restore(&mut conn, &empty).is_err());
+        assert!(restore(&mut conn, &dir.path().join("missing.db")).is_err());
+    }
+
+    #[test]
+    fn test_snapshots_rotate() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = init_db(dir.path().join("vocab.db").to_str().unwrap()).unwrap();
+        assert_eq!(snapshot(&conn, "seed").unwrap(), None);
+
+        queries::insert_word(&conn, "abound", "be plentiful", 1, "").unwrap();
+        for _ in 0..SNAPSHOT_LIMIT + 2 {
+            assert!(snapshot(&conn, "seed").unwrap().is_some());
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let count = fs::read_dir(dir.path().join(SNAPSHOT_DIR)).unwrap().count();
+        assert_eq!(count, SNAPSHOT_LIMIT);
+        assert_eq!(
+            snapshot(&init_db(":memory:").unwrap(), "seed").unwrap(),
+            None
+        );
+    }
+}
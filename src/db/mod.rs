@@ -1,16 +1,35 @@
+pub mod backup;
 pub mod models;
 pub mod queries;
 pub mod schema;
 
-use rusqlite::{Connection, Result};
-use schema::INIT_SCHEMA;
+use anyhow::Result;
+use rusqlite::Connection;
+use schema::{INIT_SCHEMA, MIGRATIONS};
 
 pub fn init_db(path: &str) -> Result<Connection> {
     let conn = Connection::open(path)?;
     conn.execute_batch(INIT_SCHEMA)?;
+    migrate(&conn)?;
     Ok(conn)
 }
 
+fn migrate(conn: &Connection) -> Result<()> {
+    let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version < MIGRATIONS.len() {
+        backup::snapshot(conn, "migrate")?;
+    }
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", i + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,4 +51,24 @@ mod tests {
         assert!(tables.contains(&"words".to_string()));
         assert!(tables.contains(&"app_state".to_string()));
     }
+
+    #[test]
+    fn test_migrations_are_applied_once() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_str().unwrap();
+
+        init_db(path).unwrap();
+        let conn = init_db(path).unwrap();
+
+        let version: usize = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+
+        conn.execute(
+            "INSERT INTO words (word, definition, group_id, tags) VALUES ('a', 'b', 1, 'x')",
+            [],
+        )
+        .unwrap();
+    }
 }
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Word {
     pub id: i32,
     pub word: String,
@@ -8,4 +10,8 @@ pub struct Word {
     pub last_seen: Option<i32>,
     pub times_seen: u8,
     pub success_count: u8,
+    #[serde(default)]
+    pub tags: String,
+    #[serde(default)]
+    pub example: Option<String>,
 }
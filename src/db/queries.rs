@@ -1,7 +1,7 @@
 use super::models::Word;
 use crate::ui::app::Screen;
 use anyhow::Result;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 
 fn screen_to_int(screen: Screen) -> i32 {
     match screen {
@@ -18,6 +18,10 @@ fn int_to_screen(v: i32) -> Screen {
     }
 }
 
+/// Column list matching the field order expected by `map_word`.
+const WORD_COLUMNS: &str = "id, word, definition, group_id,
+                marked, last_seen, times_seen, success_count, tags, example";
+
 fn map_word(row: &rusqlite::Row) -> rusqlite::Result<Word> {
     Ok(Word {
         id: row.get(0)?,
@@ -28,6 +32,8 @@ fn map_word(row: &rusqlite::Row) -> rusqlite::Result<Word> {
         last_seen: row.get(5)?,
         times_seen: row.get(6)?,
         success_count: row.get(7)?,
+        tags: row.get(8)?,
+        example: row.get(9)?,
     })
 }
 
@@ -60,11 +66,10 @@ pub fn fetch_progress(conn: &Connection) -> Result<(Screen, i32, usize)> {
 }
 
 pub fn fetch_words_by_group(conn: &Connection, group_id: i32) -> Result<Vec<Word>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, word, definition, group_id,
-                marked, last_seen, times_seen, success_count
-         FROM words WHERE group_id=?1",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {WORD_COLUMNS}
+         FROM words WHERE group_id=?1"
+    ))?;
 
     Ok(stmt
         .query_map(params![group_id], map_word)?
@@ -72,14 +77,13 @@ pub fn fetch_words_by_group(conn: &Connection, group_id: i32) -> Result<Vec<Word
 }
 
 pub fn fetch_marked_words(conn: &Connection) -> Result<Vec<Word>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, word, definition, group_id,
-                marked, last_seen, times_seen, success_count
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {WORD_COLUMNS}
          FROM words
          WHERE marked=1
          ORDER BY last_seen DESC
-         LIMIT 20",
-    )?;
+         LIMIT 20"
+    ))?;
 
     Ok(stmt
         .query_map([], map_word)?
@@ -87,20 +91,62 @@ pub fn fetch_marked_words(conn: &Connection) -> Result<Vec<Word>> {
 }
 
 pub fn fetch_weak_words(conn: &Connection) -> Result<Vec<Word>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, word, definition, group_id,
-                marked, last_seen, times_seen, success_count
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {WORD_COLUMNS}
          FROM words
          WHERE times_seen>0
          ORDER BY 1.0*success_count/times_seen ASC
-         LIMIT 20",
-    )?;
+         LIMIT 20"
+    ))?;
 
     Ok(stmt
         .query_map([], map_word)?
         .collect::<Result<Vec<_>, _>>()?)
 }
 
+pub fn fetch_all_words(conn: &Connection) -> Result<Vec<Word>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {WORD_COLUMNS}
+         FROM words
+         ORDER BY group_id, id"
+    ))?;
+
+    Ok(stmt
+        .query_map([], map_word)?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Look a word up by its text, ignoring case.
+pub fn fetch_word(conn: &Connection, word: &str) -> Result<Option<Word>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {WORD_COLUMNS}
+         FROM words
+         WHERE word=?1 COLLATE NOCASE"
+    ))?;
+
+    Ok(stmt.query_map([word], map_word)?.next().transpose()?)
+}
+
+/// Words whose text, definition, tags or example contain `text`; words that
+/// start with it come first.
+pub fn search_words(conn: &Connection, text: &str) -> Result<Vec<Word>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {WORD_COLUMNS}
+         FROM words
+         WHERE instr(lower(word), lower(?1)) > 0
+            OR instr(lower(definition), lower(?1)) > 0
+            OR instr(lower(tags), lower(?1)) > 0
+            OR instr(lower(COALESCE(example, '')), lower(?1)) > 0
+         ORDER BY instr(lower(word), lower(?1)) != 1,
+                  instr(lower(word), lower(?1)) = 0,
+                  word"
+    ))?;
+
+    Ok(stmt
+        .query_map([text], map_word)?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
 pub fn update_word_stats(conn: &Connection, word: &Word) -> Result<()> {
     conn.execute(
         "UPDATE words
@@ -121,6 +167,264 @@ pub fn update_word_stats(conn: &Connection, word: &Word) -> Result<()> {
     Ok(())
 }
 
+/// Overwrite the editable fields of a word, matched by id. Renaming onto an
+/// existing word fails with a unique constraint error.
+pub fn update_word(conn: &Connection, word: &Word) -> Result<()> {
+    conn.execute(
+        "UPDATE words
+         SET word=?1,
+             definition=?2,
+             group_id=?3,
+             marked=?4,
+             tags=?5,
+             example=?6
+         WHERE id=?7",
+        params![
+            word.word,
+            word.definition,
+            word.group_id,
+            word.marked,
+            word.tags,
+            word.example,
+            word.id
+        ],
+    )?;
+
+    Ok(())
+}
+
+pub fn delete_word(conn: &Connection, id: i32) -> Result<()> {
+    conn.execute("DELETE FROM words WHERE id=?1", [id])?;
+
+    Ok(())
+}
+
+/// Insert a new word, returning `false` when the word already exists.
+pub fn insert_word(
+    conn: &Connection,
+    word: &str,
+    definition: &str,
+    group_id: i32,
+    tags: &str,
+) -> Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO words (word, definition, group_id, tags)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![word, definition, group_id, tags],
+    )?;
+
+    Ok(inserted > 0)
+}
+
+/// Overwrite the review counters of a word, e.g. with history carried over
+/// from another app.
+pub fn set_review_counts(
+    conn: &Connection,
+    word: &str,
+    times_seen: u8,
+    success_count: u8,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE words SET times_seen=?1, success_count=?2 WHERE word=?3",
+        params![times_seen, success_count, word],
+    )?;
+
+    Ok(())
+}
+
+pub fn set_example(conn: &Connection, word: &str, example: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE words SET example=?1 WHERE word=?2",
+        params![example, word],
+    )?;
+
+    Ok(())
+}
+
+pub fn insert_review(
+    conn: &Connection,
+    word_id: i32,
+    reviewed_at: i64,
+    correct: bool,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO reviews (word_id, reviewed_at, correct) VALUES (?1, ?2, ?3)",
+        params![word_id, reviewed_at, correct],
+    )?;
+
+    Ok(())
+}
+
+/// Unix timestamps of every review since `since`, oldest first.
+pub fn fetch_review_times(conn: &Connection, since: i64) -> Result<Vec<i64>> {
+    let mut stmt =
+        conn.prepare("SELECT reviewed_at FROM reviews WHERE reviewed_at>=?1 ORDER BY reviewed_at")?;
+
+    Ok(stmt
+        .query_map(params![since], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// `(word, reviewed_at, correct)` for every review, oldest first.
+pub fn fetch_reviews(conn: &Connection) -> Result<Vec<(String, i64, bool)>> {
+    let mut stmt = conn.prepare(
+        "SELECT words.word, reviews.reviewed_at, reviews.correct
+         FROM reviews JOIN words ON words.id=reviews.word_id
+         ORDER BY reviews.reviewed_at, reviews.id",
+    )?;
+
+    Ok(stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn delete_reviews(conn: &Connection, word_id: i32) -> Result<()> {
+    conn.execute("DELETE FROM reviews WHERE word_id=?1", params![word_id])?;
+
+    Ok(())
+}
+
+/// Count a graded answer towards the totals of `day` (`YYYY-MM-DD`).
+pub fn record_activity(conn: &Connection, day: &str, new_word: bool) -> Result<()> {
+    conn.execute(
+        "INSERT INTO daily_activity (day, reviews, new_words) VALUES (?1, 1, ?2)
+         ON CONFLICT(day) DO UPDATE SET
+             reviews=reviews+1,
+             new_words=new_words+excluded.new_words",
+        params![day, new_word as i32],
+    )?;
+
+    Ok(())
+}
+
+/// `(day, reviews, new_words)` for every day with activity, oldest first.
+pub fn fetch_daily_activity(conn: &Connection) -> Result<Vec<(String, u32, u32)>> {
+    let mut stmt =
+        conn.prepare("SELECT day, reviews, new_words FROM daily_activity ORDER BY day")?;
+
+    Ok(stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Overwrite the totals of `day`.
+pub fn set_daily_activity(
+    conn: &Connection,
+    day: &str,
+    reviews: u32,
+    new_words: u32,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO daily_activity (day, reviews, new_words) VALUES (?1, ?2, ?3)
+         ON CONFLICT(day) DO UPDATE SET
+             reviews=excluded.reviews,
+             new_words=excluded.new_words",
+        params![day, reviews, new_words],
+    )?;
+
+    Ok(())
+}
+
+pub fn max_group_id(conn: &Connection) -> Result<Option<i32>> {
+    Ok(conn.query_row("SELECT MAX(group_id) FROM words", [], |row| row.get(0))?)
+}
+
+/// Insert a word with all of its stats, or overwrite the existing word with
+/// the same text.
+pub fn upsert_word(conn: &Connection, word: &Word) -> Result<()> {
+    conn.execute(
+        "INSERT INTO words (word, definition, group_id, marked,
+                            last_seen, times_seen, success_count, tags, example)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT(word) DO UPDATE SET
+             definition=excluded.definition,
+             group_id=excluded.group_id,
+             marked=excluded.marked,
+             last_seen=excluded.last_seen,
+             times_seen=excluded.times_seen,
+             success_count=excluded.success_count,
+             tags=excluded.tags,
+             example=excluded.example",
+        params![
+            word.word,
+            word.definition,
+            word.group_id,
+            word.marked,
+            word.last_seen,
+            word.times_seen,
+            word.success_count,
+            word.tags,
+            word.example
+        ],
+    )?;
+
+    Ok(())
+}
+
+pub fn fetch_app_state(conn: &Connection) -> Result<Vec<(String, i32)>> {
+    let mut stmt = conn.prepare("SELECT key, value FROM app_state ORDER BY key")?;
+
+    Ok(stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn fetch_state_value(conn: &Connection, key: &str) -> Result<Option<i32>> {
+    Ok(conn
+        .query_row(
+            "SELECT value FROM app_state WHERE key=?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+pub fn set_app_state(conn: &Connection, key: &str, value: i32) -> Result<()> {
+    upsert_state(conn, key, value)
+}
+
+pub fn fetch_settings(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare("SELECT key, value FROM settings ORDER BY key")?;
+
+    Ok(stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO settings(key,value)
+         VALUES(?1,?2)
+         ON CONFLICT(key) DO UPDATE SET value=excluded.value",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+/// Register a dictionary file, returning `false` if it was already known.
+pub fn add_dictionary(conn: &Connection, path: &str) -> Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO dictionaries (path) VALUES (?1)",
+        params![path],
+    )?;
+
+    Ok(inserted > 0)
+}
+
+pub fn remove_dictionary(conn: &Connection, path: &str) -> Result<bool> {
+    let removed = conn.execute("DELETE FROM dictionaries WHERE path=?1", params![path])?;
+
+    Ok(removed > 0)
+}
+
+pub fn fetch_dictionaries(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT path FROM dictionaries ORDER BY id")?;
+
+    Ok(stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
 fn upsert_state(conn: &Connection, key: &str, value: i32) -> Result<()> {
     conn.execute(
         "INSERT INTO app_state(key,value)
@@ -141,6 +445,82 @@ pub fn save_progress(conn: &Connection, progress: (Screen, i32, usize)) -> Resul
     Ok(())
 }
 
+/// Forget the saved position; the next session starts at Group 1.
+pub fn clear_progress(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM app_state WHERE key IN ('mode', 'group_id', 'index')",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Which words a bulk update touches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    All,
+    Group(i32),
+    Word(i32),
+}
+
+impl Scope {
+    fn clause(self) -> (&'static str, Option<i32>) {
+        match self {
+            Scope::All => ("1=1", None),
+            Scope::Group(group) => ("group_id=?1", Some(group)),
+            Scope::Word(id) => ("id=?1", Some(id)),
+        }
+    }
+}
+
+/// Zero the review counters and last-seen time and drop the review history,
+/// returning the number of words touched. The dropped reviews are taken off
+/// the daily totals too, so goals and streaks match the history.
+pub fn clear_stats(conn: &Connection, scope: Scope) -> Result<usize> {
+    let (clause, value) = scope.clause();
+    // A word's first review is the one that counted it as new
+    conn.execute(
+        &format!(
+            "WITH cleared AS (
+                 SELECT word_id, reviewed_at FROM reviews
+                 WHERE word_id IN (SELECT id FROM words WHERE {clause})
+             ),
+             days AS (
+                 SELECT date(reviewed_at, 'unixepoch', 'localtime') AS day,
+                        COUNT(*) AS reviews, 0 AS new_words
+                 FROM cleared GROUP BY 1
+                 UNION ALL
+                 SELECT date(MIN(reviewed_at), 'unixepoch', 'localtime'), 0, 1
+                 FROM cleared GROUP BY word_id
+             )
+             UPDATE daily_activity SET
+                 reviews=MAX(0, reviews-(SELECT SUM(reviews) FROM days WHERE days.day=daily_activity.day)),
+                 new_words=MAX(0, new_words-(SELECT SUM(new_words) FROM days WHERE days.day=daily_activity.day))
+             WHERE day IN (SELECT day FROM days)"
+        ),
+        rusqlite::params_from_iter(value),
+    )?;
+    conn.execute(
+        "DELETE FROM daily_activity WHERE reviews=0 AND new_words=0",
+        [],
+    )?;
+    conn.execute(
+        &format!("DELETE FROM reviews WHERE word_id IN (SELECT id FROM words WHERE {clause})"),
+        rusqlite::params_from_iter(value),
+    )?;
+    Ok(conn.execute(
+        &format!("UPDATE words SET times_seen=0, success_count=0, last_seen=NULL WHERE {clause}"),
+        rusqlite::params_from_iter(value),
+    )?)
+}
+
+pub fn clear_marks(conn: &Connection, scope: Scope) -> Result<usize> {
+    let (clause, value) = scope.clause();
+    Ok(conn.execute(
+        &format!("UPDATE words SET marked=0 WHERE {clause}"),
+        rusqlite::params_from_iter(value),
+    )?)
+}
+
 pub fn get_tutorial_completed(conn: &Connection) -> Result<bool> {
     let value: i32 = conn
         .query_row(
@@ -201,6 +581,8 @@ mod tests {
             last_seen: Some(10),
             times_seen: 5,
             success_count: 4,
+            tags: String::new(),
+            example: None,
         };
 
         update_word_stats(&conn, &w).unwrap();
@@ -17,3 +17,40 @@ CREATE TABLE IF NOT EXISTS app_state (
     value INTEGER NOT NULL
 );
 "#;
+
+/// Schema changes applied on top of `INIT_SCHEMA`, in order. The number of
+/// applied migrations is tracked in `PRAGMA user_version`, so entries must
+/// never be edited or reordered once released — only appended.
+pub const MIGRATIONS: &[&str] = &[
+    // 1: free-form tags imported from spreadsheets
+    "ALTER TABLE words ADD COLUMN tags TEXT NOT NULL DEFAULT '';",
+    // 2: usage sentence showing the word in context
+    "ALTER TABLE words ADD COLUMN example TEXT;",
+    // 3: local StarDict/dictd files used to look up definitions
+    "CREATE TABLE dictionaries (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        path TEXT NOT NULL UNIQUE
+    );",
+    // 4: one row per graded answer, for daily activity
+    "CREATE TABLE reviews (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        word_id INTEGER NOT NULL REFERENCES words(id) ON DELETE CASCADE,
+        reviewed_at INTEGER NOT NULL,
+        correct INTEGER NOT NULL
+    );
+    CREATE INDEX reviews_reviewed_at ON reviews(reviewed_at);",
+    // 5: per-day totals for the daily goal and streaks
+    "CREATE TABLE daily_activity (
+        day TEXT PRIMARY KEY,
+        reviews INTEGER NOT NULL DEFAULT 0,
+        new_words INTEGER NOT NULL DEFAULT 0
+    );
+    INSERT INTO daily_activity (day, reviews)
+        SELECT date(reviewed_at, 'unixepoch', 'localtime'), COUNT(*)
+        FROM reviews GROUP BY 1;",
+    // 6: preferences edited on the settings screen
+    "CREATE TABLE settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+];
@@ -0,0 +1,217 @@
+// dictd reader
+// Parses .index/.dict[.dz] dictionaries as served by dictd
+
+use super::{clean_text, read_maybe_compressed, with_suffix};
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const B64_DIGITS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Headword of the entry holding the dictionary's display name.
+const SHORT_NAME_ENTRY: &str = "00-database-short";
+
+pub struct Dictd {
+    pub name: String,
+    /// Lowercased headword -> (offset, size) of each entry in `data`
+    index: HashMap<String, Vec<(usize, usize)>>,
+    data: Vec<u8>,
+}
+
+impl Dictd {
+    pub fn open(base: &Path) -> Result<Self> {
+        let index_text = fs::read_to_string(with_suffix(base, ".index"))?;
+        let data = read_maybe_compressed(base, ".dict", ".dz")?;
+
+        let mut index: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (i, line) in index_text.lines().enumerate() {
+            let mut fields = line.split('\t');
+            let (Some(word), Some(offset), Some(size)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            let malformed = || anyhow!("malformed .index file at line {}", i + 1);
+            let offset = decode_b64(offset).ok_or_else(malformed)?;
+            let size = decode_b64(size).ok_or_else(malformed)?;
+            index
+                .entry(word.to_lowercase())
+                .or_default()
+                .push((offset, size));
+        }
+
+        let mut dict = Self {
+            name: base
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            index,
+            data,
+        };
+
+        // The short-name entry repeats its headword on the first line
+        if let Some(name) = dict
+            .raw_entries(SHORT_NAME_ENTRY)
+            .first()
+            .and_then(|entry| {
+                entry
+                    .lines()
+                    .map(str::trim)
+                    .find(|l| *l != SHORT_NAME_ENTRY && !l.is_empty())
+            })
+        {
+            dict.name = name.to_string();
+        }
+
+        Ok(dict)
+    }
+
+    pub fn lookup(&self, word: &str) -> Vec<String> {
+        let word = word.trim();
+        if word.starts_with("00-database") || word.starts_with("00database") {
+            return Vec::new();
+        }
+
+        self.raw_entries(word)
+            .into_iter()
+            .map(|entry| {
+                // Entries usually start with the headword on its own line
+                let mut lines = entry.lines();
+                let body = match lines.clone().next() {
+                    Some(first) if first.trim().eq_ignore_ascii_case(word) => {
+                        lines.next();
+                        lines.collect::<Vec<_>>().join("\n")
+                    }
+                    _ => entry.clone(),
+                };
+                clean_text(&body)
+            })
+            .filter(|text| !text.is_empty())
+            .collect()
+    }
+
+    fn raw_entries(&self, word: &str) -> Vec<String> {
+        self.index
+            .get(&word.to_lowercase())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|&(offset, size)| self.data.get(offset..offset.checked_add(size)?))
+                    .map(|raw| String::from_utf8_lossy(raw).into_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// dictd stores offsets as base64 *numbers*, most significant digit first.
+fn decode_b64(value: &str) -> Option<usize> {
+    value.chars().try_fold(0usize, |acc, c| {
+        let digit = B64_DIGITS.find(c)?;
+        acc.checked_mul(64)?.checked_add(digit)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_b64(mut value: usize) -> String {
+        let digits: Vec<char> = B64_DIGITS.chars().collect();
+        let mut out = Vec::new();
+        loop {
+            out.push(digits[value % 64]);
+            value /= 64;
+            if value == 0 {
+                break;
+            }
+        }
+        out.iter().rev().collect()
+    }
+
+    fn write_dict(dir: &Path, entries: &[(&str, &str)]) -> std::path::PathBuf {
+        let base = dir.join("gcide");
+        let mut index = String::new();
+        let mut data = String::new();
+
+        for (word, entry) in entries {
+            index.push_str(&format!(
+                "{word}\t{}\t{}\n",
+                encode_b64(data.len()),
+                encode_b64(entry.len())
+            ));
+            data.push_str(entry);
+        }
+
+        fs::write(with_suffix(&base, ".index"), index).unwrap();
+        fs::write(with_suffix(&base, ".dict"), data).unwrap();
+        base
+    }
+
+    #[test]
+    fn test_decode_b64() {
+        assert_eq!(decode_b64("A"), Some(0));
+        assert_eq!(decode_b64("B"), Some(1));
+        assert_eq!(decode_b64("BA"), Some(64));
+        assert_eq!(decode_b64("b!"), None);
+        assert_eq!(decode_b64(&encode_b64(123_456)), Some(123_456));
+    }
+
+    #[test]
+    fn test_lookup_strips_headword_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = write_dict(
+            dir.path(),
+            &[
+                (
+                    "00-database-short",
+                    "00-database-short\n     Test English Dictionary\n",
+                ),
+                (
+                    "capricious",
+                    "Capricious\n   Given to sudden changes of mood.\n",
+                ),
+            ],
+        );
+
+        let dict = Dictd::open(&base).unwrap();
+
+        assert_eq!(dict.name, "Test English Dictionary");
+        assert_eq!(
+            dict.lookup("Capricious"),
+            vec!["Given to sudden changes of mood."]
+        );
+        assert!(dict.lookup("00-database-short").is_empty());
+    }
+
+    #[test]
+    fn test_out_of_range_entries_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("gcide");
+        let index = format!(
+            "cursory\tA\tF\nbelie\t{}\tQ\nabound\tZ\tB\n",
+            encode_b64(usize::MAX - 4)
+        );
+        fs::write(with_suffix(&base, ".index"), index).unwrap();
+        fs::write(with_suffix(&base, ".dict"), "hasty\n").unwrap();
+
+        let dict = Dictd::open(&base).unwrap();
+
+        assert_eq!(dict.lookup("cursory"), vec!["hasty"]);
+        assert!(dict.lookup("belie").is_empty());
+        assert!(dict.lookup("abound").is_empty());
+    }
+
+    #[test]
+    fn test_name_falls_back_to_file_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = write_dict(dir.path(), &[("cursory", "hasty\n")]);
+
+        let dict = Dictd::open(&base).unwrap();
+
+        assert_eq!(dict.name, "gcide");
+        assert_eq!(dict.lookup("cursory"), vec!["hasty"]);
+    }
+}
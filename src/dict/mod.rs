@@ -0,0 +1,204 @@
+// Offline dictionary lookup
+// Reads local StarDict and dictd files registered with `dict add`
+
+pub mod dictd;
+pub mod stardict;
+
+use crate::db::queries;
+use anyhow::{Result, bail};
+use flate2::read::GzDecoder;
+use rusqlite::Connection;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub enum Dictionary {
+    StarDict(stardict::StarDict),
+    Dictd(dictd::Dictd),
+}
+
+/// A definition together with the dictionary it came from.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub dictionary: String,
+    pub text: String,
+}
+
+impl Dictionary {
+    /// Open a dictionary from any of its files (`.ifo`, `.idx`, `.index`,
+    /// `.dict`, `.dict.dz`); the format is picked from the sibling files.
+    pub fn open(path: &str) -> Result<Self> {
+        let base = base_path(Path::new(path));
+
+        if with_suffix(&base, ".ifo").exists() {
+            Ok(Dictionary::StarDict(stardict::StarDict::open(&base)?))
+        } else if with_suffix(&base, ".index").exists() {
+            Ok(Dictionary::Dictd(dictd::Dictd::open(&base)?))
+        } else {
+            bail!("{path} is not a StarDict (.ifo) or dictd (.index) dictionary")
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Dictionary::StarDict(d) => &d.name,
+            Dictionary::Dictd(d) => &d.name,
+        }
+    }
+
+    /// Case-insensitive lookup; a word can have several entries.
+    pub fn lookup(&self, word: &str) -> Vec<String> {
+        match self {
+            Dictionary::StarDict(d) => d.lookup(word),
+            Dictionary::Dictd(d) => d.lookup(word),
+        }
+    }
+}
+
+/// Every registered dictionary, searched in the order they were added.
+#[derive(Default)]
+pub struct Dictionaries(Vec<Dictionary>);
+
+impl Dictionaries {
+    /// Dictionaries that can't be opened are reported and skipped so a
+    /// moved file doesn't break seeding.
+    pub fn load(conn: &Connection) -> Result<Self> {
+        let mut dicts = Vec::new();
+        for path in queries::fetch_dictionaries(conn)? {
+            match Dictionary::open(&path) {
+                Ok(dict) => dicts.push(dict),
+                Err(e) => eprintln!("Skipping dictionary {path}: {e}"),
+            }
+        }
+        Ok(Self(dicts))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn lookup(&self, word: &str) -> Vec<Definition> {
+        self.0
+            .iter()
+            .flat_map(|dict| {
+                dict.lookup(word).into_iter().map(|text| Definition {
+                    dictionary: dict.name().to_string(),
+                    text,
+                })
+            })
+            .collect()
+    }
+
+    /// The first definition found, used to pre-fill new words.
+    pub fn first_definition(&self, word: &str) -> Option<String> {
+        self.0
+            .iter()
+            .find_map(|dict| dict.lookup(word).into_iter().next())
+    }
+}
+
+/// The registered path referring to the same dictionary as `path`, which
+/// may name any of its files (`wn.ifo` matches a registered `wn.dict.dz`).
+pub fn find_registered(conn: &Connection, path: &str) -> Result<Option<String>> {
+    let base = base_path(&absolute(path));
+    Ok(queries::fetch_dictionaries(conn)?
+        .into_iter()
+        .find(|registered| base_path(&absolute(registered)) == base))
+}
+
+/// Canonical form of `path`, or `path` itself if it no longer exists.
+pub fn absolute(path: &str) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path))
+}
+
+/// Strip the known dictionary extensions: `wordnet.dict.dz` -> `wordnet`.
+fn base_path(path: &Path) -> PathBuf {
+    let name = path.to_string_lossy();
+    for ext in [
+        ".dict.dz", ".idx.gz", ".ifo", ".idx", ".index", ".dict", ".syn",
+    ] {
+        if let Some(base) = name.strip_suffix(ext) {
+            return PathBuf::from(base);
+        }
+    }
+    path.to_path_buf()
+}
+
+fn with_suffix(base: &Path, suffix: &str) -> PathBuf {
+    let mut name = base.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Read `base.ext`, or its gzip/dictzip-compressed `base.ext.<gz_ext>`.
+fn read_maybe_compressed(base: &Path, ext: &str, gz_ext: &str) -> Result<Vec<u8>> {
+    let plain = with_suffix(base, ext);
+    if plain.exists() {
+        return Ok(fs::read(plain)?);
+    }
+
+    let compressed = with_suffix(base, &format!("{ext}{gz_ext}"));
+    if compressed.exists() {
+        let mut data = Vec::new();
+        GzDecoder::new(fs::File::open(compressed)?).read_to_end(&mut data)?;
+        return Ok(data);
+    }
+
+    bail!("missing {}", plain.display())
+}
+
+/// Tidy a raw entry: trim every line and drop blank ones.
+fn clean_text(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    #[test]
+    fn test_base_path_strips_known_extensions() {
+        assert_eq!(base_path(Path::new("a/wn.dict.dz")), PathBuf::from("a/wn"));
+        assert_eq!(base_path(Path::new("a/wn.ifo")), PathBuf::from("a/wn"));
+        assert_eq!(base_path(Path::new("a/wn")), PathBuf::from("a/wn"));
+    }
+
+    #[test]
+    fn test_unknown_files_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nothing.dict");
+        fs::write(&path, "x").unwrap();
+
+        assert!(Dictionary::open(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_missing_dictionaries_are_skipped() {
+        let conn = init_db(":memory:").unwrap();
+        queries::add_dictionary(&conn, "/does/not/exist.ifo").unwrap();
+
+        let dicts = Dictionaries::load(&conn).unwrap();
+        assert!(dicts.is_empty());
+    }
+
+    #[test]
+    fn test_find_registered_matches_sibling_files() {
+        let conn = init_db(":memory:").unwrap();
+        queries::add_dictionary(&conn, "/dicts/wn.dict.dz").unwrap();
+
+        assert_eq!(
+            find_registered(&conn, "/dicts/wn.ifo").unwrap().as_deref(),
+            Some("/dicts/wn.dict.dz")
+        );
+        assert!(
+            find_registered(&conn, "/dicts/gcide.index")
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// StarDict reader
+// Parses .ifo/.idx/.dict[.dz] dictionaries into an in-memory index
+
+use super::{clean_text, read_maybe_compressed, with_suffix};
+use crate::import::strip_html;
+use anyhow::{Result, anyhow, bail};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const IFO_MAGIC: &str = "StarDict's dict ifo file";
+
+pub struct StarDict {
+    pub name: String,
+    /// Lowercased headword -> (offset, size) of each entry in `data`
+    index: HashMap<String, Vec<(usize, usize)>>,
+    data: Vec<u8>,
+    same_type_sequence: Option<String>,
+}
+
+impl StarDict {
+    pub fn open(base: &Path) -> Result<Self> {
+        let ifo_path = with_suffix(base, ".ifo");
+        let ifo = fs::read_to_string(&ifo_path)?;
+
+        let mut lines = ifo.lines();
+        if lines.next().map(str::trim) != Some(IFO_MAGIC) {
+            bail!("{} is not a StarDict .ifo file", ifo_path.display());
+        }
+        let options: HashMap<&str, &str> = lines
+            .filter_map(|line| line.split_once('='))
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .collect();
+
+        let offset_bits = match options.get("idxoffsetbits") {
+            Some(&"64") => 64,
+            _ => 32,
+        };
+
+        let idx = read_maybe_compressed(base, ".idx", ".gz")?;
+        let data = read_maybe_compressed(base, ".dict", ".dz")?;
+
+        Ok(Self {
+            name: options
+                .get("bookname")
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| base.display().to_string()),
+            index: parse_idx(&idx, offset_bits)?,
+            data,
+            same_type_sequence: options
+                .get("sametypesequence")
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string()),
+        })
+    }
+
+    pub fn lookup(&self, word: &str) -> Vec<String> {
+        let Some(entries) = self.index.get(&word.trim().to_lowercase()) else {
+            return Vec::new();
+        };
+
+        entries
+            .iter()
+            .filter_map(|&(offset, size)| self.data.get(offset..offset.checked_add(size)?))
+            .map(|entry| decode_entry(entry, self.same_type_sequence.as_deref()))
+            .filter(|text| !text.is_empty())
+            .collect()
+    }
+}
+
+fn parse_idx(idx: &[u8], offset_bits: u32) -> Result<HashMap<String, Vec<(usize, usize)>>> {
+    let malformed = || anyhow!("malformed .idx file");
+    let offset_len = (offset_bits / 8) as usize;
+
+    let mut index: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    let mut pos = 0;
+
+    while pos < idx.len() {
+        let end = idx[pos..]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(malformed)?;
+        let word = String::from_utf8_lossy(&idx[pos..pos + end]).to_lowercase();
+        pos += end + 1;
+
+        let offset = read_be(idx.get(pos..pos + offset_len).ok_or_else(malformed)?);
+        pos += offset_len;
+        let size = read_be(idx.get(pos..pos + 4).ok_or_else(malformed)?);
+        pos += 4;
+
+        index.entry(word).or_default().push((offset, size));
+    }
+
+    Ok(index)
+}
+
+fn read_be(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |acc, &b| (acc << 8) | b as usize)
+}
+
+/// Extract the text fields of an entry. With `sametypesequence` the type
+/// markers are omitted from the data and the last field runs to the end.
+fn decode_entry(entry: &[u8], same_type_sequence: Option<&str>) -> String {
+    let mut texts = Vec::new();
+    let mut rest = entry;
+
+    match same_type_sequence {
+        Some(types) => {
+            let types: Vec<char> = types.chars().collect();
+            for (i, &kind) in types.iter().enumerate() {
+                let last = i + 1 == types.len();
+                let (field, tail) = split_field(rest, kind, last);
+                if let Some(text) = field_text(kind, field) {
+                    texts.push(text);
+                }
+                rest = tail;
+            }
+        }
+        None => {
+            while let Some((&kind, tail)) = rest.split_first() {
+                let (field, tail) = split_field(tail, kind as char, false);
+                if let Some(text) = field_text(kind as char, field) {
+                    texts.push(text);
+                }
+                rest = tail;
+            }
+        }
+    }
+
+    clean_text(&texts.join("\n"))
+}
+
+/// Lowercase types are strings (NUL-terminated unless last), uppercase
+/// types are binary blobs prefixed with their big-endian u32 size.
+fn split_field(data: &[u8], kind: char, last: bool) -> (&[u8], &[u8]) {
+    if last {
+        return (data, &[]);
+    }
+
+    if kind.is_ascii_lowercase() {
+        match data.iter().position(|&b| b == 0) {
+            Some(end) => (&data[..end], &data[end + 1..]),
+            None => (data, &[]),
+        }
+    } else {
+        let size = data.get(..4).map(read_be).unwrap_or(0);
+        let end = (4 + size).min(data.len());
+        (data.get(4..end).unwrap_or(&[]), &data[end..])
+    }
+}
+
+fn field_text(kind: char, raw: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(raw);
+    match kind {
+        // plain text, locale text
+        'm' | 'l' => Some(text.into_owned()),
+        // pango markup, html, xdxf
+        'g' | 'h' | 'x' => Some(strip_html(&text)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression, write::GzEncoder};
+    use std::io::Write;
+
+    /// Write a StarDict with the given entries (already encoded) and return its base path.
+    fn write_dict(
+        dir: &Path,
+        same_type_sequence: Option<&str>,
+        entries: &[(&str, Vec<u8>)],
+        compress: bool,
+    ) -> std::path::PathBuf {
+        let base = dir.join("test");
+        let mut idx = Vec::new();
+        let mut data = Vec::new();
+
+        for (word, entry) in entries {
+            idx.extend_from_slice(word.as_bytes());
+            idx.push(0);
+            idx.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            idx.extend_from_slice(&(entry.len() as u32).to_be_bytes());
+            data.extend_from_slice(entry);
+        }
+
+        let mut ifo = format!(
+            "{IFO_MAGIC}\nversion=2.4.2\nwordcount={}\nbookname=Test Dict\n",
+            entries.len()
+        );
+        if let Some(seq) = same_type_sequence {
+            ifo.push_str(&format!("sametypesequence={seq}\n"));
+        }
+        fs::write(with_suffix(&base, ".ifo"), ifo).unwrap();
+        fs::write(with_suffix(&base, ".idx"), idx).unwrap();
+
+        if compress {
+            let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+            gz.write_all(&data).unwrap();
+            fs::write(with_suffix(&base, ".dict.dz"), gz.finish().unwrap()).unwrap();
+        } else {
+            fs::write(with_suffix(&base, ".dict"), data).unwrap();
+        }
+
+        base
+    }
+
+    #[test]
+    fn test_same_type_sequence_lookup() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = write_dict(
+            dir.path(),
+            Some("m"),
+            &[
+                ("Abound", b"be present in large quantities".to_vec()),
+                ("belie", b"give a false impression of".to_vec()),
+            ],
+            false,
+        );
+
+        let dict = StarDict::open(&base).unwrap();
+
+        assert_eq!(dict.name, "Test Dict");
+        assert_eq!(
+            dict.lookup("abound"),
+            vec!["be present in large quantities"]
+        );
+        assert_eq!(dict.lookup("BELIE"), vec!["give a false impression of"]);
+        assert!(dict.lookup("missing").is_empty());
+    }
+
+    #[test]
+    fn test_typed_fields_and_dictzip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut entry = b"t/ab/\0h<b>strict</b><br>stern\0".to_vec();
+        entry.extend_from_slice(b"W\0\0\0\x02\xff\xfe");
+        let base = write_dict(dir.path(), None, &[("austere", entry)], true);
+
+        let dict = StarDict::open(&base).unwrap();
+
+        // Phonetics and binary fields are skipped, HTML is flattened
+        assert_eq!(dict.lookup("austere"), vec!["strict\nstern"]);
+    }
+
+    #[test]
+    fn test_multiple_entries_for_one_word() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = write_dict(
+            dir.path(),
+            Some("m"),
+            &[
+                ("bank", b"edge of a river".to_vec()),
+                ("bank", b"money lender".to_vec()),
+            ],
+            false,
+        );
+
+        let dict = StarDict::open(&base).unwrap();
+
+        assert_eq!(dict.lookup("bank").len(), 2);
+    }
+
+    #[test]
+    fn test_out_of_range_entries_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("test");
+        let mut idx = Vec::new();
+        for (word, offset, size) in [("abound", 0u64, 5u32), ("belie", u64::MAX - 4, 16)] {
+            idx.extend_from_slice(word.as_bytes());
+            idx.push(0);
+            idx.extend_from_slice(&offset.to_be_bytes());
+            idx.extend_from_slice(&size.to_be_bytes());
+        }
+        let ifo =
+            format!("{IFO_MAGIC}\nbookname=Test Dict\nidxoffsetbits=64\nsametypesequence=m\n");
+        fs::write(with_suffix(&base, ".ifo"), ifo).unwrap();
+        fs::write(with_suffix(&base, ".idx"), idx).unwrap();
+        fs::write(with_suffix(&base, ".dict"), "teeming").unwrap();
+
+        let dict = StarDict::open(&base).unwrap();
+
+        assert_eq!(dict.lookup("abound"), vec!["teemi"]);
+        assert!(dict.lookup("belie").is_empty());
+    }
+
+    #[test]
+    fn test_rejects_bad_ifo() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("bad");
+        fs::write(with_suffix(&base, ".ifo"), "not a dictionary\n").unwrap();
+
+        assert!(StarDict::open(&base).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+// Database doctor
+// Finds inconsistent state left by crashes, hand edits or old versions and repairs it
+
+use crate::db::{backup, queries};
+use crate::dict::Dictionaries;
+use anyhow::Result;
+use rusqlite::Connection;
+use std::collections::BTreeSet;
+use std::io::Write;
+
+/// How many words a problem lists before summarising the rest.
+const LIST_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// Lines reported by `PRAGMA integrity_check`
+    Corrupt(Vec<String>),
+    /// The saved position points past the end of its group, or at a group
+    /// without words
+    Position {
+        group: i32,
+        index: usize,
+        words: usize,
+    },
+    /// Words answered correctly more often than they were seen
+    Counts(Vec<String>),
+    EmptyDefinitions(Vec<String>),
+    /// Groups numbered below 1, which no session reaches
+    OrphanGroups {
+        groups: Vec<i32>,
+        words: usize,
+    },
+}
+
+impl Problem {
+    pub fn describe(&self) -> String {
+        match self {
+            Problem::Corrupt(lines) => format!(
+                "integrity check failed: {}",
+                lines
+                    .iter()
+                    .take(LIST_LIMIT)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
+            Problem::Position {
+                group, words: 0, ..
+            } => format!("the saved position is in Group {group}, which has no words"),
+            Problem::Position {
+                group,
+                index,
+                words,
+            } => format!(
+                "the saved position {} is past the end of Group {group} ({words} words)",
+                index + 1
+            ),
+            Problem::Counts(words) => format!(
+                "{} answered correctly more often than seen: {}",
+                count(words.len()),
+                list(words)
+            ),
+            Problem::EmptyDefinitions(words) => {
+                format!(
+                    "{} without a definition: {}",
+                    count(words.len()),
+                    list(words)
+                )
+            }
+            Problem::OrphanGroups { groups, words } => format!(
+                "{} in groups numbered below 1: {}",
+                count(*words),
+                groups
+                    .iter()
+                    .map(|g| g.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Repair the problem, returning what was done, or `None` if it needs
+    /// the user's attention.
+    pub fn fix(&self, conn: &Connection, dictionaries: &Dictionaries) -> Result<Option<String>> {
+        match self {
+            Problem::Corrupt(_) => Ok(None),
+            Problem::Position { group, words, .. } => {
+                let (screen, _, _) = queries::fetch_progress(conn)?;
+                let first = queries::fetch_all_words(conn)?
+                    .iter()
+                    .map(|w| w.group_id)
+                    .filter(|g| *g >= 1)
+                    .min();
+                let group = if *words > 0 { Some(*group) } else { first };
+                match group {
+                    Some(group) => {
+                        queries::save_progress(conn, (screen, group, 0))?;
+                        Ok(Some(format!(
+                            "moved the saved position to the start of Group {group}"
+                        )))
+                    }
+                    None => {
+                        queries::clear_progress(conn)?;
+                        Ok(Some("cleared the saved position".into()))
+                    }
+                }
+            }
+            Problem::Counts(words) => {
+                for word in queries::fetch_all_words(conn)? {
+                    if word.success_count > word.times_seen {
+                        queries::set_review_counts(
+                            conn,
+                            &word.word,
+                            word.times_seen,
+                            word.times_seen,
+                        )?;
+                    }
+                }
+                Ok(Some(format!(
+                    "capped correct answers at times seen for {}",
+                    count(words.len())
+                )))
+            }
+            Problem::EmptyDefinitions(words) => {
+                let mut filled = 0;
+                for word in words {
+                    if let Some(mut found) = queries::fetch_word(conn, word)?
+                        && let Some(definition) = dictionaries.first_definition(word)
+                    {
+                        found.definition = definition;
+                        queries::update_word(conn, &found)?;
+                        filled += 1;
+                    }
+                }
+                Ok((filled > 0).then(|| format!("filled {} from dictionaries", count(filled))))
+            }
+            Problem::OrphanGroups { words, .. } => {
+                let group = queries::max_group_id(conn)?.unwrap_or(0).max(0) + 1;
+                for mut word in queries::fetch_all_words(conn)? {
+                    if word.group_id < 1 {
+                        word.group_id = group;
+                        queries::update_word(conn, &word)?;
+                    }
+                }
+                Ok(Some(format!("moved {} to Group {group}", count(*words))))
+            }
+        }
+    }
+}
+
+/// Every problem found in the database.
+pub fn diagnose(conn: &Connection) -> Result<Vec<Problem>> {
+    let mut problems = Vec::new();
+
+    let integrity: Vec<String> = conn
+        .prepare("PRAGMA integrity_check")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    if integrity != ["ok"] {
+        problems.push(Problem::Corrupt(integrity));
+    }
+
+    let words = queries::fetch_all_words(conn)?;
+    if !words.is_empty() {
+        let (_, group, index) = queries::fetch_progress(conn)?;
+        let in_group = words.iter().filter(|w| w.group_id == group).count();
+        if index >= in_group {
+            problems.push(Problem::Position {
+                group,
+                index,
+                words: in_group,
+            });
+        }
+    }
+
+    let counts: Vec<String> = words
+        .iter()
+        .filter(|w| w.success_count > w.times_seen)
+        .map(|w| w.word.clone())
+        .collect();
+    if !counts.is_empty() {
+        problems.push(Problem::Counts(counts));
+    }
+
+    let empty: Vec<String> = words
+        .iter()
+        .filter(|w| w.definition.trim().is_empty())
+        .map(|w| w.word.clone())
+        .collect();
+    if !empty.is_empty() {
+        problems.push(Problem::EmptyDefinitions(empty));
+    }
+
+    let orphans: Vec<i32> = words
+        .iter()
+        .map(|w| w.group_id)
+        .filter(|g| *g < 1)
+        .collect();
+    if !orphans.is_empty() {
+        problems.push(Problem::OrphanGroups {
+            words: orphans.len(),
+            groups: orphans
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        });
+    }
+
+    Ok(problems)
+}
+
+/// Report problems and, with `fix`, repair them in one transaction after
+/// taking a snapshot. Returns how many problems remain.
+pub fn run<W: Write>(conn: &Connection, fix: bool, output: &mut W) -> Result<usize> {
+    let problems = diagnose(conn)?;
+    if problems.is_empty() {
+        writeln!(output, "No problems found.")?;
+        return Ok(0);
+    }
+    for problem in &problems {
+        writeln!(output, "- {}", problem.describe())?;
+    }
+    if !fix {
+        return Ok(problems.len());
+    }
+
+    // Writing to a damaged file can make things worse
+    if problems.iter().any(|p| matches!(p, Problem::Corrupt(_))) {
+        writeln!(
+            output,
+            "\nThe database file is damaged. Restore a backup or a snapshot with `restore <path>`."
+        )?;
+        return Ok(problems.len());
+    }
+
+    writeln!(output)?;
+    if let Some(path) = backup::snapshot(conn, "doctor")? {
+        writeln!(output, "Saved a snapshot to {}", path.display())?;
+    }
+
+    let dictionaries = Dictionaries::load(conn)?;
+    let tx = conn.unchecked_transaction()?;
+    for problem in &problems {
+        match problem.fix(&tx, &dictionaries)? {
+            Some(done) => writeln!(output, "Fixed: {done}")?,
+            None => writeln!(output, "Not fixed: {}", problem.describe())?,
+        }
+    }
+    tx.commit()?;
+
+    Ok(diagnose(conn)?.len())
+}
+
+fn count(n: usize) -> String {
+    if n == 1 {
+        "1 word".into()
+    } else {
+        format!("{n} words")
+    }
+}
+
+fn list(words: &[String]) -> String {
+    let mut shown = words[..words.len().min(LIST_LIMIT)].join(", ");
+    if words.len() > LIST_LIMIT {
+        shown.push_str(&format!(" and {} more", words.len() - LIST_LIMIT));
+    }
+    shown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+    use crate::ui::app::Screen;
+
+    fn setup() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        queries::insert_word(&conn, "abound", "be plentiful", 1, "").unwrap();
+        queries::insert_word(&conn, "austere", "strict", 1, "").unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_healthy_database() {
+        let conn = setup();
+        let mut output = Vec::new();
+
+        assert_eq!(run(&conn, false, &mut output).unwrap(), 0);
+        assert_eq!(String::from_utf8(output).unwrap(), "No problems found.\n");
+    }
+
+    #[test]
+    fn test_finds_each_problem() {
+        let conn = setup();
+        queries::insert_word(&conn, "belie", " ", 0, "").unwrap();
+        queries::set_review_counts(&conn, "abound", 2, 5).unwrap();
+        queries::save_progress(&conn, (Screen::Test, 1, 7)).unwrap();
+
+        let problems = diagnose(&conn).unwrap();
+
+        assert_eq!(
+            problems,
+            vec![
+                Problem::Position {
+                    group: 1,
+                    index: 7,
+                    words: 2
+                },
+                Problem::Counts(vec!["abound".into()]),
+                Problem::EmptyDefinitions(vec!["belie".into()]),
+                Problem::OrphanGroups {
+                    groups: vec![0],
+                    words: 1
+                },
+            ]
+        );
+        assert_eq!(
+            problems[0].describe(),
+            "the saved position 8 is past the end of Group 1 (2 words)"
+        );
+    }
+
+    #[test]
+    fn test_fix_repairs_what_it_can() {
+        let conn = setup();
+        queries::insert_word(&conn, "belie", "", 0, "").unwrap();
+        queries::set_review_counts(&conn, "abound", 2, 5).unwrap();
+        queries::save_progress(&conn, (Screen::Test, 4, 0)).unwrap();
+
+        let mut output = Vec::new();
+        let left = run(&conn, true, &mut output).unwrap();
+
+        // Without a dictionary the empty definition stays
+        assert_eq!(
+            diagnose(&conn).unwrap(),
+            vec![Problem::EmptyDefinitions(vec!["belie".into()])]
+        );
+        assert_eq!(left, 1);
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Test, 1, 0)
+        );
+        assert_eq!(
+            queries::fetch_word(&conn, "belie")
+                .unwrap()
+                .unwrap()
+                .group_id,
+            2
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Fixed: moved 1 word to Group 2"));
+        assert!(output.contains("Not fixed: 1 word without a definition: belie"));
+    }
+
+    #[test]
+    fn test_long_lists_are_summarised() {
+        let words: Vec<String> = (0..8).map(|i| format!("w{i}")).collect();
+
+        assert_eq!(list(&words), "w0, w1, w2, w3, w4 and 3 more");
+    }
+}
@@ -0,0 +1,332 @@
+// Export module
+// Writes words and progress as CSV, Anki-importable TSV, or a JSON snapshot
+// that `import` restores without losing any stats
+
+use crate::db::{models::Word, queries};
+use anyhow::Result;
+use clap::ValueEnum;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+
+/// Bumped whenever the snapshot layout changes.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Csv,
+    Json,
+    AnkiTsv,
+}
+
+/// Which words to export. Filters combine, so `group` + `marked` exports the
+/// marked words of one group.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub group: Option<i32>,
+    pub marked: bool,
+    /// Same selection as the "Revise Weak" session
+    pub weak: bool,
+}
+
+/// Everything needed to rebuild a database: all words with their stats and
+/// review history, the daily totals, the saved `app_state` and settings.
+/// Tables added after version 1 are empty when reading older snapshots.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub words: Vec<Word>,
+    pub app_state: BTreeMap<String, i32>,
+    #[serde(default)]
+    pub reviews: Vec<Review>,
+    #[serde(default)]
+    pub daily_activity: Vec<DailyActivity>,
+    #[serde(default)]
+    pub settings: BTreeMap<String, String>,
+}
+
+/// One graded answer, tied to its word by text since ids differ between
+/// databases.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    pub word: String,
+    pub reviewed_at: i64,
+    pub correct: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyActivity {
+    pub day: String,
+    pub reviews: u32,
+    pub new_words: u32,
+}
+
+pub fn select_words(conn: &Connection, filter: &Filter) -> Result<Vec<Word>> {
+    let weak: Option<HashSet<i32>> = if filter.weak {
+        Some(
+            queries::fetch_weak_words(conn)?
+                .into_iter()
+                .map(|w| w.id)
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    Ok(queries::fetch_all_words(conn)?
+        .into_iter()
+        .filter(|w| filter.group.is_none_or(|g| w.group_id == g))
+        .filter(|w| !filter.marked || w.marked)
+        .filter(|w| weak.as_ref().is_none_or(|ids| ids.contains(&w.id)))
+        .collect())
+}
+
+/// Write the selected words to `out`, returning how many were exported.
+pub fn export<W: Write>(
+    conn: &Connection,
+    format: Format,
+    filter: &Filter,
+    out: &mut W,
+) -> Result<usize> {
+    let words = select_words(conn, filter)?;
+
+    match format {
+        Format::Csv => write_csv(&words, out)?,
+        Format::AnkiTsv => write_anki_tsv(&words, out)?,
+        Format::Json => {
+            let exported: HashSet<&str> = words.iter().map(|w| w.word.as_str()).collect();
+            let reviews = queries::fetch_reviews(conn)?
+                .into_iter()
+                .filter(|(word, _, _)| exported.contains(word.as_str()))
+                .map(|(word, reviewed_at, correct)| Review {
+                    word,
+                    reviewed_at,
+                    correct,
+                })
+                .collect();
+            let daily_activity = queries::fetch_daily_activity(conn)?
+                .into_iter()
+                .map(|(day, reviews, new_words)| DailyActivity {
+                    day,
+                    reviews,
+                    new_words,
+                })
+                .collect();
+            let snapshot = Snapshot {
+                version: SNAPSHOT_VERSION,
+                words: words.clone(),
+                app_state: queries::fetch_app_state(conn)?.into_iter().collect(),
+                reviews,
+                daily_activity,
+                settings: queries::fetch_settings(conn)?.into_iter().collect(),
+            };
+            serde_json::to_writer_pretty(&mut *out, &snapshot)?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(words.len())
+}
+
+/// Columns match what `import --format csv` expects by default.
+fn write_csv<W: Write>(words: &[Word], out: &mut W) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record([
+        "word",
+        "definition",
+        "group",
+        "tags",
+        "marked",
+        "times_seen",
+        "success_count",
+        "last_seen",
+        "example",
+    ])?;
+
+    for w in words {
+        writer.write_record([
+            w.word.clone(),
+            w.definition.clone(),
+            w.group_id.to_string(),
+            w.tags.clone(),
+            (w.marked as u8).to_string(),
+            w.times_seen.to_string(),
+            w.success_count.to_string(),
+            w.last_seen.map(|t| t.to_string()).unwrap_or_default(),
+            w.example.clone().unwrap_or_default(),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Anki's "Notes in Plain Text" format: front, back and tags separated by
+/// tabs, with the header lines telling Anki how to read the file.
+fn write_anki_tsv<W: Write>(words: &[Word], out: &mut W) -> Result<()> {
+    writeln!(out, "#separator:tab")?;
+    writeln!(out, "#html:true")?;
+    writeln!(out, "#tags column:3")?;
+
+    for w in words {
+        writeln!(
+            out,
+            "{}\t{}\t{}",
+            anki_field(&w.word),
+            anki_field(&w.definition),
+            w.tags.replace('\t', " ")
+        )?;
+    }
+
+    Ok(())
+}
+
+fn anki_field(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\t', " ")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn setup() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        queries::insert_word(&conn, "abound", "plentiful", 1, "gre").unwrap();
+        queries::insert_word(&conn, "austere", "strict\nlacking <luxury>", 1, "").unwrap();
+        queries::insert_word(&conn, "belie", "mask", 2, "").unwrap();
+        conn.execute(
+            "UPDATE words SET marked=1, times_seen=4, success_count=1, last_seen=100
+             WHERE word='belie'",
+            [],
+        )
+        .unwrap();
+        queries::set_example(&conn, "belie", "His smile belied his anger.").unwrap();
+        queries::set_app_state(&conn, "group_id", 2).unwrap();
+        let belie = queries::fetch_word(&conn, "belie").unwrap().unwrap();
+        queries::insert_review(&conn, belie.id, 100, true).unwrap();
+        queries::insert_review(&conn, belie.id, 200, false).unwrap();
+        queries::record_activity(&conn, "2024-05-12", true).unwrap();
+        queries::set_setting(&conn, "theme", "colorblind").unwrap();
+        conn
+    }
+
+    fn export_string(conn: &Connection, format: Format, filter: &Filter) -> String {
+        let mut out = Vec::new();
+        export(conn, format, filter, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_filters_combine() {
+        let conn = setup();
+
+        let group = Filter {
+            group: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(select_words(&conn, &group).unwrap().len(), 2);
+
+        let marked = Filter {
+            marked: true,
+            ..Default::default()
+        };
+        assert_eq!(select_words(&conn, &marked).unwrap()[0].word, "belie");
+
+        let none = Filter {
+            group: Some(1),
+            weak: true,
+            ..Default::default()
+        };
+        assert!(select_words(&conn, &none).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_csv_quotes_multiline_definitions() {
+        let conn = setup();
+
+        let csv = export_string(&conn, Format::Csv, &Filter::default());
+
+        assert!(csv.starts_with("word,definition,group,tags,"));
+        assert!(csv.contains("austere,\"strict\nlacking <luxury>\",1,"));
+        assert!(csv.contains("belie,mask,2,,1,4,1,100,"));
+    }
+
+    #[test]
+    fn test_anki_tsv_escapes_html() {
+        let conn = setup();
+
+        let tsv = export_string(&conn, Format::AnkiTsv, &Filter::default());
+
+        assert!(tsv.starts_with("#separator:tab\n"));
+        assert!(tsv.contains("austere\tstrict<br>lacking &lt;luxury&gt;\t\n"));
+        assert!(tsv.contains("abound\tplentiful\tgre\n"));
+    }
+
+    #[test]
+    fn test_json_round_trips_stats_and_state() {
+        let conn = setup();
+        let json = export_string(&conn, Format::Json, &Filter::default());
+
+        let restored = init_db(":memory:").unwrap();
+        let snapshot: Snapshot = serde_json::from_str(&json).unwrap();
+        crate::import::json::import_snapshot(&restored, &snapshot).unwrap();
+
+        let original = queries::fetch_all_words(&conn).unwrap();
+        let copy = queries::fetch_all_words(&restored).unwrap();
+        assert_eq!(original.len(), copy.len());
+        for (a, b) in original.iter().zip(&copy) {
+            assert_eq!(
+                (&a.word, &a.definition, a.group_id, a.marked, a.last_seen),
+                (&b.word, &b.definition, b.group_id, b.marked, b.last_seen)
+            );
+            assert_eq!(
+                (a.times_seen, a.success_count, &a.tags, &a.example),
+                (b.times_seen, b.success_count, &b.tags, &b.example)
+            );
+        }
+
+        assert_eq!(
+            queries::fetch_app_state(&restored).unwrap(),
+            vec![("group_id".to_string(), 2)]
+        );
+        assert_eq!(
+            queries::fetch_reviews(&restored).unwrap(),
+            vec![
+                ("belie".to_string(), 100, true),
+                ("belie".to_string(), 200, false)
+            ]
+        );
+        assert_eq!(
+            queries::fetch_daily_activity(&restored).unwrap(),
+            vec![("2024-05-12".to_string(), 1, 1)]
+        );
+        assert_eq!(
+            queries::fetch_settings(&restored).unwrap(),
+            vec![("theme".to_string(), "colorblind".to_string())]
+        );
+
+        // Importing again replaces the history instead of doubling it
+        crate::import::json::import_snapshot(&restored, &snapshot).unwrap();
+        assert_eq!(queries::fetch_reviews(&restored).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_version_1_snapshots_still_import() {
+        let restored = init_db(":memory:").unwrap();
+        let json = r#"{"version": 1, "words": [{"id": 1, "word": "abound", "definition": "plentiful",
+            "group_id": 1, "marked": false, "last_seen": null, "times_seen": 0,
+            "success_count": 0}], "app_state": {}}"#;
+
+        let snapshot: Snapshot = serde_json::from_str(json).unwrap();
+        crate::import::json::import_snapshot(&restored, &snapshot).unwrap();
+
+        assert_eq!(queries::fetch_all_words(&restored).unwrap().len(), 1);
+        assert!(queries::fetch_reviews(&restored).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+// EPUB reader
+// Pulls the chapter text out of an EPUB in reading (spine) order
+
+use crate::import::strip_html;
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+pub fn read_file(path: &str) -> Result<String> {
+    let archive = ZipArchive::new(File::open(path)?)
+        .with_context(|| format!("{path} is not an EPUB (zip) file"))?;
+    read_archive(archive)
+}
+
+fn read_archive<R: Read + Seek>(mut archive: ZipArchive<R>) -> Result<String> {
+    let chapters = match read_entry(&mut archive, "META-INF/container.xml")
+        .ok()
+        .and_then(|container| attr_of(&container, "rootfile", "full-path"))
+    {
+        Some(opf_path) => spine(&read_entry(&mut archive, &opf_path)?, &opf_path),
+        // No container: fall back to every HTML file in name order
+        None => {
+            let mut names: Vec<String> = archive
+                .file_names()
+                .filter(|n| n.ends_with(".xhtml") || n.ends_with(".html") || n.ends_with(".htm"))
+                .map(String::from)
+                .collect();
+            names.sort();
+            names
+        }
+    };
+
+    if chapters.is_empty() {
+        bail!("no chapters found in the EPUB");
+    }
+
+    let mut text = String::new();
+    for chapter in chapters {
+        // Missing chapters are skipped; broken books are common
+        let Ok(html) = read_entry(&mut archive, &chapter) else {
+            continue;
+        };
+        let body = match html.find("<body") {
+            Some(start) => &html[start..],
+            None => &html,
+        };
+        text.push_str(&strip_html(body));
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+/// Chapter paths from the OPF package, in spine order and relative to the
+/// archive root.
+fn spine(opf: &str, opf_path: &str) -> Vec<String> {
+    let dir = match opf_path.rfind('/') {
+        Some(i) => &opf_path[..=i],
+        None => "",
+    };
+
+    let manifest: HashMap<String, String> = tags(opf, "item")
+        .filter_map(|tag| Some((attr(tag, "id")?, attr(tag, "href")?)))
+        .collect();
+
+    tags(opf, "itemref")
+        .filter_map(|tag| attr(tag, "idref"))
+        .filter_map(|id| manifest.get(&id))
+        .map(|href| format!("{dir}{}", percent_decode(href)))
+        .collect()
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<String> {
+    let mut content = String::new();
+    archive.by_name(name)?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Every `<name ...>` tag in `xml`, namespace prefixes ignored.
+fn tags<'a>(xml: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    xml.split('<').filter_map(move |chunk| {
+        let tag = &chunk[..chunk.find('>')?];
+        let tag_name = tag.split_whitespace().next()?;
+        let local = tag_name.rsplit(':').next()?.trim_end_matches('/');
+        (local == name).then_some(tag)
+    })
+}
+
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(pos) = rest.find(name) {
+        let before = rest[..pos].chars().last();
+        let after = rest[pos + name.len()..].trim_start();
+        rest = &rest[pos + name.len()..];
+
+        if before.is_some_and(|c| !c.is_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            continue;
+        }
+        let value = &value[1..];
+        return Some(value[..value.find(quote)?].to_string());
+    }
+    None
+}
+
+fn attr_of(xml: &str, tag_name: &str, name: &str) -> Option<String> {
+    tags(xml, tag_name).find_map(|tag| attr(tag, name))
+}
+
+/// Decode `%20`-style escapes in manifest hrefs.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    fn epub(files: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        ZipArchive::new(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_chapters_follow_spine_order() {
+        let archive = epub(&[
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+            ),
+            (
+                "OEBPS/content.opf",
+                r#"<package><manifest>
+                    <item id="c1" href="Text/one.xhtml" media-type="application/xhtml+xml"/>
+                    <item id="c2" href="Text/two%20b.xhtml" media-type="application/xhtml+xml"/>
+                   </manifest><spine><itemref idref="c2"/><itemref idref="c1"/></spine></package>"#,
+            ),
+            (
+                "OEBPS/Text/one.xhtml",
+                "<html><head><title>Skip me</title></head><body><p>First chapter.</p></body></html>",
+            ),
+            (
+                "OEBPS/Text/two b.xhtml",
+                "<html><body><p>Second&nbsp;chapter.</p></body></html>",
+            ),
+        ]);
+
+        let text = read_archive(archive).unwrap();
+
+        assert!(!text.contains("Skip me"));
+        let second = text.find("Second chapter.").unwrap();
+        let first = text.find("First chapter.").unwrap();
+        assert!(second < first);
+    }
+
+    #[test]
+    fn test_without_container_reads_html_files() {
+        let archive = epub(&[("b.html", "<p>Beta</p>"), ("a.html", "<p>Alpha</p>")]);
+
+        let text = read_archive(archive).unwrap();
+
+        assert!(text.find("Alpha").unwrap() < text.find("Beta").unwrap());
+    }
+
+    #[test]
+    fn test_attr_parsing() {
+        let tag = r#"item  id='x' data-id="no" href = "a.xhtml""#;
+        assert_eq!(attr(tag, "id").as_deref(), Some("x"));
+        assert_eq!(attr(tag, "href").as_deref(), Some("a.xhtml"));
+        assert_eq!(attr(tag, "src"), None);
+    }
+}
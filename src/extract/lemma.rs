@@ -0,0 +1,280 @@
+// Lemmatiser
+// Reduces inflected English words to their dictionary form with suffix rules
+
+/// Irregular forms of common words, mapped to their lemma.
+const IRREGULAR: &[(&str, &str)] = &[
+    ("am", "be"),
+    ("is", "be"),
+    ("are", "be"),
+    ("was", "be"),
+    ("were", "be"),
+    ("been", "be"),
+    ("being", "be"),
+    ("has", "have"),
+    ("had", "have"),
+    ("does", "do"),
+    ("did", "do"),
+    ("done", "do"),
+    ("went", "go"),
+    ("gone", "go"),
+    ("said", "say"),
+    ("made", "make"),
+    ("took", "take"),
+    ("taken", "take"),
+    ("came", "come"),
+    ("saw", "see"),
+    ("seen", "see"),
+    ("knew", "know"),
+    ("known", "know"),
+    ("got", "get"),
+    ("gotten", "get"),
+    ("gave", "give"),
+    ("given", "give"),
+    ("found", "find"),
+    ("thought", "think"),
+    ("told", "tell"),
+    ("became", "become"),
+    ("felt", "feel"),
+    ("brought", "bring"),
+    ("began", "begin"),
+    ("begun", "begin"),
+    ("kept", "keep"),
+    ("held", "hold"),
+    ("wrote", "write"),
+    ("written", "write"),
+    ("stood", "stand"),
+    ("heard", "hear"),
+    ("meant", "mean"),
+    ("ran", "run"),
+    ("paid", "pay"),
+    ("sat", "sit"),
+    ("spoke", "speak"),
+    ("spoken", "speak"),
+    ("led", "lead"),
+    ("grew", "grow"),
+    ("grown", "grow"),
+    ("lost", "lose"),
+    ("fell", "fall"),
+    ("fallen", "fall"),
+    ("sent", "send"),
+    ("built", "build"),
+    ("understood", "understand"),
+    ("drew", "draw"),
+    ("drawn", "draw"),
+    ("broke", "break"),
+    ("spent", "spend"),
+    ("risen", "rise"),
+    ("drove", "drive"),
+    ("driven", "drive"),
+    ("bought", "buy"),
+    ("wore", "wear"),
+    ("worn", "wear"),
+    ("chose", "choose"),
+    ("chosen", "choose"),
+    ("sought", "seek"),
+    ("threw", "throw"),
+    ("thrown", "throw"),
+    ("caught", "catch"),
+    ("dealt", "deal"),
+    ("won", "win"),
+    ("forgot", "forget"),
+    ("forgotten", "forget"),
+    ("ate", "eat"),
+    ("eaten", "eat"),
+    ("fought", "fight"),
+    ("taught", "teach"),
+    ("slept", "sleep"),
+    ("flew", "fly"),
+    ("flown", "fly"),
+    ("sang", "sing"),
+    ("sung", "sing"),
+    ("swam", "swim"),
+    ("hid", "hide"),
+    ("hidden", "hide"),
+    ("shook", "shake"),
+    ("shaken", "shake"),
+    ("woke", "wake"),
+    ("struck", "strike"),
+    ("stole", "steal"),
+    ("stolen", "steal"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("children", "child"),
+    ("feet", "foot"),
+    ("teeth", "tooth"),
+    ("mice", "mouse"),
+    ("better", "good"),
+    ("best", "good"),
+    ("worse", "bad"),
+    ("worst", "bad"),
+    ("lives", "life"),
+    ("wives", "wife"),
+    ("knives", "knife"),
+    ("leaves", "leaf"),
+    ("selves", "self"),
+    ("halves", "half"),
+    ("loaves", "loaf"),
+    ("thieves", "thief"),
+    ("wolves", "wolf"),
+    ("shelves", "shelf"),
+    ("calves", "calf"),
+    ("hooves", "hoof"),
+    ("scarves", "scarf"),
+    ("wharves", "wharf"),
+];
+
+/// Stem endings that almost always drop a silent "e" before -ed/-ing
+/// (`obfuscated` -> `obfuscate`, `derived` -> `derive`).
+const SILENT_E_ENDINGS: &[&str] = &[
+    "at", "bl", "iz", "yz", "rc", "nc", "rg", "dg", "iv", "ev", "ov", "uc", "ur", "os", "ud",
+];
+
+/// Reduce a lowercase word to its lemma. Candidate stems are checked with
+/// `known` first; when none is known, the suffix rules decide on their own.
+pub fn lemmatize(word: &str, known: impl Fn(&str) -> bool) -> String {
+    if let Some((_, lemma)) = IRREGULAR.iter().find(|(form, _)| *form == word) {
+        return lemma.to_string();
+    }
+    if known(word) {
+        return word.to_string();
+    }
+    if let Some(lemma) = candidates(word).into_iter().find(|c| known(c)) {
+        return lemma;
+    }
+    default_stem(word)
+}
+
+/// Every plausible lemma for an inflected word, most likely first.
+fn candidates(word: &str) -> Vec<String> {
+    let mut out = Vec::new();
+
+    if let Some(base) = word.strip_suffix("ies") {
+        out.push(format!("{base}y"));
+    }
+    if let Some(base) = word.strip_suffix("ves") {
+        out.push(format!("{base}f"));
+        out.push(format!("{base}fe"));
+    }
+    if let Some(base) = word.strip_suffix("es") {
+        out.push(base.to_string());
+    }
+    if let Some(base) = word.strip_suffix('s') {
+        out.push(base.to_string());
+    }
+    if let Some(base) = word.strip_suffix("ied") {
+        out.push(format!("{base}y"));
+    }
+    for suffix in ["ed", "ing", "er", "est"] {
+        if let Some(base) = word.strip_suffix(suffix) {
+            out.push(base.to_string());
+            out.push(format!("{base}e"));
+            if let Some(undoubled) = undouble(base) {
+                out.push(undoubled);
+            }
+        }
+    }
+    if let Some(base) = word.strip_suffix("ier") {
+        out.push(format!("{base}y"));
+    }
+    if let Some(base) = word.strip_suffix("iest") {
+        out.push(format!("{base}y"));
+    }
+
+    out.retain(|c| c.len() >= 2);
+    out
+}
+
+/// Suffix stripping for words no word list knows about.
+fn default_stem(word: &str) -> String {
+    if word.len() > 4 {
+        if let Some(base) = word.strip_suffix("ies") {
+            return format!("{base}y");
+        }
+        if let Some(base) = word.strip_suffix("ied") {
+            return format!("{base}y");
+        }
+    }
+
+    if let Some(base) = word.strip_suffix("es")
+        && ["s", "x", "z", "ch", "sh"]
+            .iter()
+            .any(|e| base.ends_with(e))
+        && base.len() >= 3
+    {
+        return base.to_string();
+    }
+    if let Some(base) = word.strip_suffix('s')
+        && !["s", "u", "i"].iter().any(|e| base.ends_with(e))
+        && base.len() >= 3
+    {
+        return base.to_string();
+    }
+
+    for suffix in ["ing", "ed"] {
+        if let Some(base) = word.strip_suffix(suffix)
+            && base.len() >= 4
+            && base.chars().any(is_vowel)
+        {
+            if let Some(undoubled) = undouble(base) {
+                return undoubled;
+            }
+            if SILENT_E_ENDINGS.iter().any(|e| base.ends_with(e)) {
+                return format!("{base}e");
+            }
+            return base.to_string();
+        }
+    }
+
+    word.to_string()
+}
+
+/// `stopp` -> `stop`; l, s and z are legitimately doubled (`dull`, `pass`).
+fn undouble(base: &str) -> Option<String> {
+    let mut chars = base.chars().rev();
+    let (last, prev) = (chars.next()?, chars.next()?);
+    (last == prev && !is_vowel(last) && !"lsz".contains(last))
+        .then(|| base[..base.len() - last.len_utf8()].to_string())
+}
+
+fn is_vowel(c: char) -> bool {
+    "aeiouy".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lemma(word: &str) -> String {
+        lemmatize(word, |w| {
+            ["study", "run", "hope", "box", "knife"].contains(&w)
+        })
+    }
+
+    #[test]
+    fn test_known_stems_are_preferred() {
+        assert_eq!(lemma("studies"), "study");
+        assert_eq!(lemma("running"), "run");
+        assert_eq!(lemma("hoped"), "hope");
+        assert_eq!(lemma("boxes"), "box");
+        assert_eq!(lemma("knives"), "knife");
+    }
+
+    #[test]
+    fn test_irregular_forms() {
+        assert_eq!(lemma("was"), "be");
+        assert_eq!(lemma("children"), "child");
+    }
+
+    #[test]
+    fn test_rules_for_unknown_words() {
+        assert_eq!(lemma("abounded"), "abound");
+        assert_eq!(lemma("obfuscated"), "obfuscate");
+        assert_eq!(lemma("alleviating"), "alleviate");
+        assert_eq!(lemma("abetted"), "abet");
+        assert_eq!(lemma("vexes"), "vex");
+        assert_eq!(lemma("harbingers"), "harbinger");
+        assert_eq!(lemma("panacea"), "panacea");
+        assert_eq!(lemma("conspicuous"), "conspicuous");
+        assert_eq!(lemma("naked"), "naked");
+    }
+}
@@ -0,0 +1,265 @@
+// Vocabulary extraction
+// Finds uncommon words in a text or EPUB file to turn what you read into a deck
+
+pub mod epub;
+pub mod lemma;
+
+use crate::db::queries;
+use crate::dict::Dictionaries;
+use anyhow::Result;
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+/// Bundled list of common English lemmas, most frequent first.
+const COMMON_WORDS: &str = include_str!("../../data/common_words.txt");
+
+/// Examples longer than this are cut at a word boundary.
+const MAX_EXAMPLE_LEN: usize = 200;
+
+/// A word worth learning, with the first sentence it appeared in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub word: String,
+    pub count: usize,
+    pub sentence: String,
+}
+
+/// Read a plain text or EPUB file as one paragraph per line.
+pub fn read_source(path: &str) -> Result<String> {
+    if path.to_lowercase().ends_with(".epub") {
+        return epub::read_file(path);
+    }
+
+    // Plain text is usually hard-wrapped, with blank lines between paragraphs
+    let text = fs::read_to_string(path)?;
+    Ok(text
+        .split("\n\n")
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Lemmatised words from `text` that are neither common nor already in the
+/// database, rarest first: fewest occurrences, then longest.
+pub fn find_candidates(
+    conn: &Connection,
+    text: &str,
+    dictionaries: &Dictionaries,
+) -> Result<Vec<Candidate>> {
+    let common = common_words();
+    let existing: HashSet<String> = queries::fetch_all_words(conn)?
+        .into_iter()
+        .map(|w| w.word.to_lowercase())
+        .collect();
+
+    let known = |word: &str| {
+        common.contains(word)
+            || existing.contains(word)
+            || dictionaries.first_definition(word).is_some()
+    };
+
+    // Words only ever seen capitalised are treated as names
+    let tokens: Vec<(String, &str)> = sentences(text)
+        .flat_map(|sentence| tokens(sentence).map(move |token| (token, sentence)))
+        .collect();
+    let lowercase: HashSet<&str> = tokens
+        .iter()
+        .filter(|(token, _)| token.starts_with(|c: char| c.is_lowercase()))
+        .map(|(token, _)| token.as_str())
+        .collect();
+
+    let mut lemmas: HashMap<String, String> = HashMap::new();
+    let mut found: HashMap<String, Candidate> = HashMap::new();
+    let mut order = Vec::new();
+
+    for (token, sentence) in &tokens {
+        let lower = token.to_lowercase();
+        if !lowercase.contains(lower.as_str()) {
+            continue;
+        }
+
+        let word = lemmas
+            .entry(lower.clone())
+            .or_insert_with(|| lemma::lemmatize(&lower, known))
+            .clone();
+        if common.contains(word.as_str()) || existing.contains(&word) {
+            continue;
+        }
+
+        match found.get_mut(&word) {
+            Some(candidate) => candidate.count += 1,
+            None => {
+                order.push(word.clone());
+                found.insert(
+                    word.clone(),
+                    Candidate {
+                        word,
+                        count: 1,
+                        sentence: shorten(sentence),
+                    },
+                );
+            }
+        }
+    }
+
+    let mut candidates: Vec<Candidate> = order
+        .into_iter()
+        .filter_map(|word| found.remove(&word))
+        .collect();
+    candidates.sort_by(|a, b| {
+        a.count
+            .cmp(&b.count)
+            .then(b.word.chars().count().cmp(&a.word.chars().count()))
+            .then(a.word.cmp(&b.word))
+    });
+
+    Ok(candidates)
+}
+
+/// Add the chosen candidates to `group`, keeping their sentence as the
+/// example and taking the definition from the dictionaries when possible.
+/// Returns how many were added.
+pub fn add_candidates(
+    conn: &Connection,
+    candidates: &[&Candidate],
+    group: i32,
+    dictionaries: &Dictionaries,
+) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut added = 0;
+
+    for candidate in candidates {
+        let definition = dictionaries
+            .first_definition(&candidate.word)
+            .unwrap_or_default();
+        if queries::insert_word(&tx, &candidate.word, &definition, group, "")? {
+            queries::set_example(&tx, &candidate.word, &candidate.sentence)?;
+            added += 1;
+        }
+    }
+
+    tx.commit()?;
+    Ok(added)
+}
+
+fn common_words() -> HashSet<&'static str> {
+    COMMON_WORDS
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Split paragraphs (one per line) into sentences at `.`, `!` and `?`.
+fn sentences(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().flat_map(|paragraph| {
+        let mut out = Vec::new();
+        let mut start = 0;
+        let mut chars = paragraph.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if matches!(c, '.' | '!' | '?')
+                && chars.peek().is_none_or(|(_, next)| next.is_whitespace())
+            {
+                out.push(paragraph[start..=i].trim());
+                start = i + 1;
+            }
+        }
+        out.push(paragraph[start..].trim());
+        out.into_iter().filter(|s| !s.is_empty())
+    })
+}
+
+/// Alphabetic words of three or more letters; contractions and
+/// possessives keep only the part before the apostrophe.
+fn tokens(sentence: &str) -> impl Iterator<Item = String> + '_ {
+    sentence
+        .split(|c: char| !(c.is_alphabetic() || c == '\'' || c == '’'))
+        .filter_map(|token| token.split(['\'', '’']).next())
+        .filter(|token| token.chars().count() >= 3)
+        .map(String::from)
+}
+
+fn shorten(sentence: &str) -> String {
+    if sentence.len() <= MAX_EXAMPLE_LEN {
+        return sentence.to_string();
+    }
+    let mut end = MAX_EXAMPLE_LEN;
+    while !sentence.is_char_boundary(end) {
+        end -= 1;
+    }
+    let cut = sentence[..end].rfind(' ').unwrap_or(end);
+    format!("{}...", &sentence[..cut])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn words(candidates: &[Candidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.word.as_str()).collect()
+    }
+
+    #[test]
+    fn test_common_existing_and_names_are_dropped() {
+        let conn = init_db(":memory:").unwrap();
+        queries::insert_word(&conn, "austere", "strict", 1, "").unwrap();
+
+        let text = "Ishmael found the austere room. The leviathans abounded there!\n\
+                    Ishmael nodded; leviathans were ubiquitous.";
+        let candidates = find_candidates(&conn, text, &Dictionaries::default()).unwrap();
+
+        assert_eq!(
+            words(&candidates),
+            vec!["ubiquitous", "abound", "leviathan"]
+        );
+        assert_eq!(candidates[2].count, 2);
+        assert_eq!(candidates[1].sentence, "The leviathans abounded there!");
+    }
+
+    #[test]
+    fn test_add_candidates_keeps_sentence() {
+        let conn = init_db(":memory:").unwrap();
+        let candidate = Candidate {
+            word: "laconic".into(),
+            count: 1,
+            sentence: "His reply was laconic.".into(),
+        };
+
+        let added = add_candidates(&conn, &[&candidate], 4, &Dictionaries::default()).unwrap();
+        let again = add_candidates(&conn, &[&candidate], 4, &Dictionaries::default()).unwrap();
+
+        let (group, example): (i32, String) = conn
+            .query_row(
+                "SELECT group_id, example FROM words WHERE word='laconic'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((added, again), (1, 0));
+        assert_eq!((group, example.as_str()), (4, "His reply was laconic."));
+    }
+
+    #[test]
+    fn test_sentences_and_tokens() {
+        let sentences: Vec<&str> = sentences("One 3.5 two. Three? Four\nFive").collect();
+        assert_eq!(sentences, vec!["One 3.5 two.", "Three?", "Four", "Five"]);
+
+        let tokens: Vec<String> = tokens("The captain’s log wasn't read-only").collect();
+        assert_eq!(
+            tokens,
+            vec!["The", "captain", "log", "wasn", "read", "only"]
+        );
+    }
+
+    #[test]
+    fn test_long_sentences_are_shortened() {
+        let sentence = "word ".repeat(100);
+        let short = shorten(sentence.trim());
+
+        assert!(short.len() <= MAX_EXAMPLE_LEN + 3);
+        assert!(short.ends_with("word..."));
+    }
+}
@@ -0,0 +1,567 @@
+// Anki package import
+// Reads notes, note types and decks from the SQLite collection inside a .apkg
+
+use super::{ImportReport, strip_html};
+use crate::db::queries;
+use anyhow::{Result, anyhow, bail};
+use rusqlite::{Connection, OpenFlags};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufRead, Read, Write};
+use tempfile::NamedTempFile;
+use zip::ZipArchive;
+
+/// Collection files in the order they are preferred. Recent Anki versions
+/// ship a zstd-compressed `anki21b` next to a stub `anki2` for old clients.
+const COLLECTION_FILES: &[&str] = &[
+    "collection.anki21b",
+    "collection.anki21",
+    "collection.anki2",
+];
+
+const FIELD_SEPARATOR: char = '\x1f';
+
+#[derive(Debug, Clone)]
+pub struct NoteType {
+    pub id: i64,
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Field holding the word (name or 1-based number); asked for when unset
+    pub word_field: Option<String>,
+    /// Field holding the definition; asked for when unset
+    pub def_field: Option<String>,
+    /// Carry Anki review counts over into `times_seen`/`success_count`
+    pub with_reviews: bool,
+}
+
+/// An Anki collection extracted from a package into a temporary file.
+pub struct Collection {
+    conn: Connection,
+    note_types: Vec<NoteType>,
+    decks: HashMap<i64, String>,
+    // Keeps the extracted database alive for as long as `conn` uses it
+    _file: NamedTempFile,
+}
+
+impl Collection {
+    pub fn open(path: &str) -> Result<Self> {
+        let mut archive = ZipArchive::new(File::open(path)?)?;
+
+        let name = COLLECTION_FILES
+            .iter()
+            .find(|name| archive.by_name(name).is_ok())
+            .ok_or_else(|| anyhow!("{path} does not contain an Anki collection"))?;
+
+        let mut data = Vec::new();
+        archive.by_name(name)?.read_to_end(&mut data)?;
+        if name.ends_with("anki21b") {
+            data = zstd::decode_all(data.as_slice())?;
+        }
+
+        let mut file = NamedTempFile::new()?;
+        file.write_all(&data)?;
+        file.flush()?;
+
+        let conn = Connection::open_with_flags(file.path(), OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let (note_types, decks) = if has_table(&conn, "notetypes")? {
+            (load_note_types(&conn)?, load_decks(&conn)?)
+        } else {
+            load_legacy_models_and_decks(&conn)?
+        };
+
+        Ok(Self {
+            conn,
+            note_types,
+            decks,
+            _file: file,
+        })
+    }
+
+    fn deck_name(&self, id: i64) -> String {
+        self.decks
+            .get(&id)
+            .map(|name| name.replace(FIELD_SEPARATOR, "::"))
+            .unwrap_or_else(|| format!("Deck {id}"))
+    }
+}
+
+struct Note {
+    id: i64,
+    note_type: i64,
+    fields: Vec<String>,
+    tags: String,
+    deck: i64,
+    reps: i64,
+    lapses: i64,
+}
+
+pub fn import_file<R: BufRead, W: Write>(
+    conn: &Connection,
+    path: &str,
+    options: &Options,
+    input: &mut R,
+    output: &mut W,
+) -> Result<ImportReport> {
+    let collection = Collection::open(path)?;
+    let notes = fetch_notes(&collection.conn)?;
+
+    // Pick the word/definition fields for every note type actually used
+    let mut choices: HashMap<i64, (usize, usize)> = HashMap::new();
+    for note_type in &collection.note_types {
+        if !notes.iter().any(|n| n.note_type == note_type.id) {
+            continue;
+        }
+
+        let word = choose_field(
+            note_type,
+            options.word_field.as_deref(),
+            "Word",
+            0,
+            input,
+            output,
+        )?;
+        let definition = choose_field(
+            note_type,
+            options.def_field.as_deref(),
+            "Definition",
+            note_type.fields.len().saturating_sub(1).min(1),
+            input,
+            output,
+        )?;
+        choices.insert(note_type.id, (word, definition));
+    }
+
+    // Every deck becomes a new group, numbered after the existing ones
+    let deck_names: BTreeSet<String> = notes.iter().map(|n| collection.deck_name(n.deck)).collect();
+    let first_group = queries::max_group_id(conn)?.unwrap_or(0) + 1;
+    let groups: HashMap<String, i32> = deck_names.into_iter().zip(first_group..).collect();
+
+    let mut mapping: Vec<_> = groups.iter().collect();
+    mapping.sort_by_key(|(_, group)| **group);
+    for (deck, group) in mapping {
+        writeln!(output, "Deck \"{deck}\" -> Group {group}")?;
+    }
+
+    let mut report = ImportReport::default();
+    let tx = conn.unchecked_transaction()?;
+
+    for note in &notes {
+        let location = format!("note {}", note.id);
+        let Some(&(word_idx, def_idx)) = choices.get(&note.note_type) else {
+            report.reject(location, "unknown note type");
+            continue;
+        };
+
+        let word = note
+            .fields
+            .get(word_idx)
+            .map(|f| strip_html(f))
+            .unwrap_or_default();
+        let word = word.split_whitespace().collect::<Vec<_>>().join(" ");
+        if word.is_empty() {
+            report.reject(location, "missing word");
+            continue;
+        }
+
+        let definition = note
+            .fields
+            .get(def_idx)
+            .map(|f| strip_html(f))
+            .unwrap_or_default();
+        if definition.is_empty() {
+            report.reject(location, format!("\"{word}\" has no definition"));
+            continue;
+        }
+
+        let group_id = groups[&collection.deck_name(note.deck)];
+        if !queries::insert_word(&tx, &word, &definition, group_id, note.tags.trim())? {
+            report.reject(location, format!("\"{word}\" already exists"));
+            continue;
+        }
+
+        if options.with_reviews && note.reps > 0 {
+            let times_seen = note.reps.clamp(0, u8::MAX as i64) as u8;
+            let success = (note.reps - note.lapses).clamp(0, times_seen as i64) as u8;
+            queries::set_review_counts(&tx, &word, times_seen, success)?;
+        }
+
+        report.imported += 1;
+    }
+
+    tx.commit()?;
+    Ok(report)
+}
+
+fn fetch_notes(conn: &Connection) -> Result<Vec<Note>> {
+    let mut stmt = conn.prepare(
+        "SELECT n.id, n.mid, n.flds, n.tags,
+                (SELECT c.did FROM cards c WHERE c.nid = n.id ORDER BY c.ord LIMIT 1),
+                (SELECT COALESCE(SUM(c.reps), 0) FROM cards c WHERE c.nid = n.id),
+                (SELECT COALESCE(SUM(c.lapses), 0) FROM cards c WHERE c.nid = n.id)
+         FROM notes n
+         ORDER BY n.id",
+    )?;
+
+    let notes = stmt
+        .query_map([], |row| {
+            let flds: String = row.get(2)?;
+            Ok(Note {
+                id: row.get(0)?,
+                note_type: row.get(1)?,
+                fields: flds.split(FIELD_SEPARATOR).map(str::to_string).collect(),
+                tags: row.get(3)?,
+                deck: row.get::<_, Option<i64>>(4)?.unwrap_or(1),
+                reps: row.get(5)?,
+                lapses: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(notes)
+}
+
+fn has_table(conn: &Connection, name: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
+        [name],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+fn load_note_types(conn: &Connection) -> Result<Vec<NoteType>> {
+    let mut stmt = conn.prepare("SELECT id, name FROM notetypes ORDER BY id")?;
+    let mut note_types = stmt
+        .query_map([], |row| {
+            Ok(NoteType {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                fields: Vec::new(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut stmt = conn.prepare("SELECT name FROM fields WHERE ntid=?1 ORDER BY ord")?;
+    for note_type in &mut note_types {
+        note_type.fields = stmt
+            .query_map([note_type.id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+    }
+
+    Ok(note_types)
+}
+
+fn load_decks(conn: &Connection) -> Result<HashMap<i64, String>> {
+    let mut stmt = conn.prepare("SELECT id, name FROM decks")?;
+    let decks = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(decks)
+}
+
+/// Collections before schema 15 keep note types and decks as JSON blobs in `col`.
+fn load_legacy_models_and_decks(
+    conn: &Connection,
+) -> Result<(Vec<NoteType>, HashMap<i64, String>)> {
+    let (models, decks): (String, String) =
+        conn.query_row("SELECT models, decks FROM col", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+
+    let models: HashMap<String, Value> = serde_json::from_str(&models)?;
+    let mut note_types = Vec::new();
+    for (id, model) in models {
+        let mut fields: Vec<(i64, String)> = model["flds"]
+            .as_array()
+            .map(|flds| {
+                flds.iter()
+                    .map(|f| {
+                        (
+                            f["ord"].as_i64().unwrap_or(0),
+                            f["name"].as_str().unwrap_or("").to_string(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        fields.sort_by_key(|(ord, _)| *ord);
+
+        note_types.push(NoteType {
+            id: id.parse()?,
+            name: model["name"].as_str().unwrap_or("").to_string(),
+            fields: fields.into_iter().map(|(_, name)| name).collect(),
+        });
+    }
+    note_types.sort_by_key(|n| n.id);
+
+    let decks: HashMap<String, Value> = serde_json::from_str(&decks)?;
+    let decks = decks
+        .into_iter()
+        .map(|(id, deck)| Ok((id.parse()?, deck["name"].as_str().unwrap_or("").to_string())))
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    Ok((note_types, decks))
+}
+
+/// Resolve `spec` against the note type's fields, or ask the user when no
+/// field was given on the command line.
+fn choose_field<R: BufRead, W: Write>(
+    note_type: &NoteType,
+    spec: Option<&str>,
+    label: &str,
+    default: usize,
+    input: &mut R,
+    output: &mut W,
+) -> Result<usize> {
+    if note_type.fields.is_empty() {
+        bail!("note type \"{}\" has no fields", note_type.name);
+    }
+
+    if let Some(spec) = spec {
+        return resolve_field(note_type, spec).ok_or_else(|| {
+            anyhow!(
+                "note type \"{}\" has no field \"{spec}\" (fields: {})",
+                note_type.name,
+                note_type.fields.join(", ")
+            )
+        });
+    }
+
+    writeln!(output, "Note type \"{}\" has fields:", note_type.name)?;
+    for (i, field) in note_type.fields.iter().enumerate() {
+        writeln!(output, "  {}) {field}", i + 1)?;
+    }
+
+    loop {
+        write!(output, "{label} field [{}]: ", default + 1)?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            bail!(
+                "no {} field chosen for \"{}\"",
+                label.to_lowercase(),
+                note_type.name
+            );
+        }
+
+        let answer = line.trim();
+        if answer.is_empty() {
+            return Ok(default);
+        }
+        match resolve_field(note_type, answer) {
+            Some(idx) => return Ok(idx),
+            None => writeln!(output, "No such field: {answer}")?,
+        }
+    }
+}
+
+fn resolve_field(note_type: &NoteType, spec: &str) -> Option<usize> {
+    let spec = spec.trim();
+    if let Some(idx) = note_type
+        .fields
+        .iter()
+        .position(|f| f.eq_ignore_ascii_case(spec))
+    {
+        return Some(idx);
+    }
+
+    match spec.parse::<usize>() {
+        Ok(n) if n >= 1 && n <= note_type.fields.len() => Some(n - 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+    use std::io::Cursor;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    fn legacy_collection() -> Vec<u8> {
+        let file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(file.path()).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE col (id INTEGER PRIMARY KEY, models TEXT NOT NULL, decks TEXT NOT NULL);
+            CREATE TABLE notes (id INTEGER PRIMARY KEY, mid INTEGER, flds TEXT, tags TEXT);
+            CREATE TABLE cards (id INTEGER PRIMARY KEY, nid INTEGER, did INTEGER, ord INTEGER,
+                                reps INTEGER, lapses INTEGER);
+            INSERT INTO col VALUES (1,
+                '{"100": {"name": "Basic", "flds": [{"name": "Back", "ord": 1}, {"name": "Front", "ord": 0}]}}',
+                '{"1": {"name": "Default"}, "20": {"name": "GRE::Hard"}}');
+            INSERT INTO notes VALUES (1, 100, 'abound' || char(31) || 'be <b>plentiful</b>', ' gre ');
+            INSERT INTO notes VALUES (2, 100, 'belie' || char(31) || 'mask<br>contradict', '');
+            INSERT INTO notes VALUES (3, 100, '' || char(31) || 'no word', '');
+            INSERT INTO cards VALUES (1, 1, 20, 0, 5, 2);
+            INSERT INTO cards VALUES (2, 2, 1, 0, 0, 0);
+            INSERT INTO cards VALUES (3, 3, 1, 0, 0, 0);
+            "#,
+        )
+        .unwrap();
+        drop(conn);
+        std::fs::read(file.path()).unwrap()
+    }
+
+    fn modern_collection() -> Vec<u8> {
+        let file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(file.path()).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE notetypes (id INTEGER PRIMARY KEY, name TEXT);
+            CREATE TABLE fields (ntid INTEGER, ord INTEGER, name TEXT);
+            CREATE TABLE decks (id INTEGER PRIMARY KEY, name TEXT);
+            CREATE TABLE notes (id INTEGER PRIMARY KEY, mid INTEGER, flds TEXT, tags TEXT);
+            CREATE TABLE cards (id INTEGER PRIMARY KEY, nid INTEGER, did INTEGER, ord INTEGER,
+                                reps INTEGER, lapses INTEGER);
+            INSERT INTO notetypes VALUES (7, 'Vocab');
+            INSERT INTO fields VALUES (7, 0, 'Term'), (7, 1, 'Example'), (7, 2, 'Meaning');
+            INSERT INTO decks VALUES (3, 'Words');
+            INSERT INTO notes VALUES (1, 7, 'capricious' || char(31) || 'a capricious wind' || char(31) || 'fickle', '');
+            INSERT INTO cards VALUES (1, 1, 3, 0, 1, 0);
+            "#,
+        )
+        .unwrap();
+        drop(conn);
+        std::fs::read(file.path()).unwrap()
+    }
+
+    fn package(name: &str, collection: &[u8]) -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        let mut zip = ZipWriter::new(File::create(file.path()).unwrap());
+        zip.start_file(name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(collection).unwrap();
+        zip.finish().unwrap();
+        file
+    }
+
+    fn run(
+        conn: &Connection,
+        file: &NamedTempFile,
+        options: &Options,
+        answers: &str,
+    ) -> ImportReport {
+        let mut output = Vec::new();
+        import_file(
+            conn,
+            file.path().to_str().unwrap(),
+            options,
+            &mut Cursor::new(answers),
+            &mut output,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_legacy_package_with_explicit_fields() {
+        let conn = init_db(":memory:").unwrap();
+        let file = package("collection.anki2", &legacy_collection());
+
+        let options = Options {
+            word_field: Some("front".into()),
+            def_field: Some("Back".into()),
+            with_reviews: false,
+        };
+        let report = run(&conn, &file, &options, "");
+
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].location, "note 3");
+
+        let (definition, tags, times_seen): (String, String, i32) = conn
+            .query_row(
+                "SELECT definition, tags, times_seen FROM words WHERE word='abound'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(definition, "be plentiful");
+        assert_eq!(tags, "gre");
+        assert_eq!(times_seen, 0);
+    }
+
+    #[test]
+    fn test_decks_map_to_new_groups() {
+        let conn = init_db(":memory:").unwrap();
+        queries::insert_word(&conn, "existing", "already here", 4, "").unwrap();
+        let file = package("collection.anki2", &legacy_collection());
+
+        run(&conn, &file, &Options::default(), "1\n2\n");
+
+        let group_of = |word: &str| -> i32 {
+            conn.query_row("SELECT group_id FROM words WHERE word=?1", [word], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        // Decks are numbered in name order after the highest existing group
+        assert_eq!(group_of("belie"), 5); // Default
+        assert_eq!(group_of("abound"), 6); // GRE::Hard
+    }
+
+    #[test]
+    fn test_reviews_are_carried_over() {
+        let conn = init_db(":memory:").unwrap();
+        let file = package("collection.anki2", &legacy_collection());
+
+        let options = Options {
+            with_reviews: true,
+            ..Default::default()
+        };
+        run(&conn, &file, &options, "\n\n");
+
+        let (times_seen, success): (i32, i32) = conn
+            .query_row(
+                "SELECT times_seen, success_count FROM words WHERE word='abound'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((times_seen, success), (5, 3));
+    }
+
+    #[test]
+    fn test_compressed_modern_package_prompts_for_fields() {
+        let conn = init_db(":memory:").unwrap();
+        let compressed = zstd::encode_all(modern_collection().as_slice(), 0).unwrap();
+        let file = package("collection.anki21b", &compressed);
+
+        // Bad answer first, then pick "Meaning" by name
+        let report = run(&conn, &file, &Options::default(), "\nnope\nmeaning\n");
+
+        assert_eq!(report.imported, 1);
+        let definition: String = conn
+            .query_row(
+                "SELECT definition FROM words WHERE word='capricious'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(definition, "fickle");
+    }
+
+    #[test]
+    fn test_unknown_field_is_an_error() {
+        let conn = init_db(":memory:").unwrap();
+        let file = package("collection.anki2", &legacy_collection());
+
+        let options = Options {
+            word_field: Some("Lemma".into()),
+            ..Default::default()
+        };
+        let result = import_file(
+            &conn,
+            file.path().to_str().unwrap(),
+            &options,
+            &mut Cursor::new(""),
+            &mut Vec::new(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,262 @@
+// CSV/TSV import
+// Maps spreadsheet columns (by header name or 1-based position) onto words
+
+use super::ImportReport;
+use crate::db::queries;
+use anyhow::{Result, anyhow};
+use csv::{ReaderBuilder, StringRecord};
+use rusqlite::Connection;
+use std::fs;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Which source column feeds each field. Each entry is a header name
+/// (case-insensitive) or a 1-based column number.
+#[derive(Debug, Clone)]
+pub struct ColumnMap {
+    pub word: String,
+    pub definition: String,
+    pub group: Option<String>,
+    pub tags: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub delimiter: u8,
+    pub columns: ColumnMap,
+    /// Group used for rows without a group column
+    pub default_group: i32,
+}
+
+pub fn import_file(conn: &Connection, path: &str, options: &Options) -> Result<ImportReport> {
+    let data = fs::read(path)?;
+    import_bytes(conn, &data, options)
+}
+
+pub fn import_bytes(conn: &Connection, data: &[u8], options: &Options) -> Result<ImportReport> {
+    let data = data.strip_prefix(UTF8_BOM).unwrap_or(data);
+
+    let mut reader = ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(data);
+
+    let headers = reader.headers()?.clone();
+    let columns = &options.columns;
+    let word_col = resolve_column(&headers, &columns.word)?;
+    let def_col = resolve_column(&headers, &columns.definition)?;
+    let group_col = columns
+        .group
+        .as_deref()
+        .map(|c| resolve_column(&headers, c))
+        .transpose()?;
+    let tags_col = columns
+        .tags
+        .as_deref()
+        .map(|c| resolve_column(&headers, c))
+        .transpose()?;
+
+    let mut report = ImportReport::default();
+    let tx = conn.unchecked_transaction()?;
+
+    for result in reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(0);
+                report.reject(line, e.to_string());
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+
+        let word = field(&record, word_col)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if word.is_empty() {
+            report.reject(line, "missing word");
+            continue;
+        }
+
+        let definition = field(&record, def_col);
+        if definition.is_empty() {
+            report.reject(line, format!("\"{word}\" has no definition"));
+            continue;
+        }
+
+        let group_id = match group_col.map(|c| field(&record, c)) {
+            None | Some("") => options.default_group,
+            Some(raw) => match raw.parse::<i32>() {
+                Ok(id) => id,
+                Err(_) => {
+                    report.reject(line, format!("\"{word}\" has invalid group \"{raw}\""));
+                    continue;
+                }
+            },
+        };
+
+        let tags = tags_col.map(|c| field(&record, c)).unwrap_or("");
+
+        if queries::insert_word(&tx, &word, definition, group_id, tags)? {
+            report.imported += 1;
+        } else {
+            report.reject(line, format!("\"{word}\" already exists"));
+        }
+    }
+
+    tx.commit()?;
+    Ok(report)
+}
+
+fn field(record: &StringRecord, col: usize) -> &str {
+    record.get(col).unwrap_or("").trim()
+}
+
+fn resolve_column(headers: &StringRecord, spec: &str) -> Result<usize> {
+    let spec = spec.trim();
+
+    if let Some(idx) = headers
+        .iter()
+        .position(|h| h.trim().eq_ignore_ascii_case(spec))
+    {
+        return Ok(idx);
+    }
+
+    match spec.parse::<usize>() {
+        Ok(n) if n >= 1 && n <= headers.len() => Ok(n - 1),
+        _ => Err(anyhow!(
+            "unknown column \"{spec}\", available columns: {}",
+            headers.iter().collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn options(delimiter: u8) -> Options {
+        Options {
+            delimiter,
+            columns: ColumnMap {
+                word: "word".into(),
+                definition: "definition".into(),
+                group: Some("group".into()),
+                tags: Some("tags".into()),
+            },
+            default_group: 1,
+        }
+    }
+
+    fn definition_of(conn: &Connection, word: &str) -> String {
+        conn.query_row(
+            "SELECT definition FROM words WHERE word=?1",
+            [word],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_csv_quoting_and_embedded_newlines() {
+        let conn = init_db(":memory:").unwrap();
+
+        let data = "word,definition,group,tags\n\
+                    abound,\"be plentiful, abundant\",2,gre\n\
+                    austere,\"strict and stern\nlacking luxury\",2,\"gre core\"\n";
+
+        let report = import_bytes(&conn, data.as_bytes(), &options(b',')).unwrap();
+
+        assert_eq!(report.imported, 2);
+        assert!(report.rejected.is_empty());
+        assert_eq!(definition_of(&conn, "abound"), "be plentiful, abundant");
+        assert_eq!(
+            definition_of(&conn, "austere"),
+            "strict and stern\nlacking luxury"
+        );
+
+        let tags: String = conn
+            .query_row("SELECT tags FROM words WHERE word='austere'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(tags, "gre core");
+    }
+
+    #[test]
+    fn test_tsv_with_bom_and_header_case() {
+        let conn = init_db(":memory:").unwrap();
+
+        let data = "\u{feff}Word\tDefinition\tGroup\tTags\nbona fide\tgenuine\t3\t\n";
+
+        let report = import_bytes(&conn, data.as_bytes(), &options(b'\t')).unwrap();
+
+        assert_eq!(report.imported, 1);
+        let group_id: i32 = conn
+            .query_row(
+                "SELECT group_id FROM words WHERE word='bona fide'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(group_id, 3);
+    }
+
+    #[test]
+    fn test_columns_by_position_and_default_group() {
+        let conn = init_db(":memory:").unwrap();
+
+        let data = "term,meaning\nbelie,give a false impression\n";
+        let mut opts = options(b',');
+        opts.columns = ColumnMap {
+            word: "1".into(),
+            definition: "meaning".into(),
+            group: None,
+            tags: None,
+        };
+        opts.default_group = 7;
+
+        let report = import_bytes(&conn, data.as_bytes(), &opts).unwrap();
+
+        assert_eq!(report.imported, 1);
+        let group_id: i32 = conn
+            .query_row("SELECT group_id FROM words", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(group_id, 7);
+    }
+
+    #[test]
+    fn test_rejected_rows_are_reported() {
+        let conn = init_db(":memory:").unwrap();
+
+        let data = "word,definition,group,tags\n\
+                    abound,plentiful,1,\n\
+                    ,no word,1,\n\
+                    belie,,1,\n\
+                    capricious,fickle,one,\n\
+                    abound,again,1,\n";
+
+        let report = import_bytes(&conn, data.as_bytes(), &options(b',')).unwrap();
+
+        assert_eq!(report.imported, 1);
+        let lines: Vec<&str> = report
+            .rejected
+            .iter()
+            .map(|r| r.location.as_str())
+            .collect();
+        assert_eq!(lines, vec!["3", "4", "5", "6"]);
+    }
+
+    #[test]
+    fn test_unknown_column_is_an_error() {
+        let conn = init_db(":memory:").unwrap();
+
+        let mut opts = options(b',');
+        opts.columns.word = "lemma".into();
+
+        let err = import_bytes(&conn, b"word,definition\n", &opts).unwrap_err();
+        assert!(err.to_string().contains("lemma"));
+    }
+}
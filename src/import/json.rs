@@ -0,0 +1,70 @@
+// JSON snapshot import
+// Restores words, stats, review history, daily totals, app state and
+// settings written by `export --format json`
+
+use super::ImportReport;
+use crate::db::queries;
+use crate::export::{SNAPSHOT_VERSION, Snapshot};
+use anyhow::{Result, bail};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+
+pub fn import_file(conn: &Connection, path: &str) -> Result<ImportReport> {
+    let snapshot: Snapshot = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    import_snapshot(conn, &snapshot)
+}
+
+/// Words are matched on their text: existing words are overwritten with the
+/// snapshot's definition, stats and review history, new ones are added.
+pub fn import_snapshot(conn: &Connection, snapshot: &Snapshot) -> Result<ImportReport> {
+    if snapshot.version > SNAPSHOT_VERSION {
+        bail!(
+            "snapshot version {} is newer than this app supports ({SNAPSHOT_VERSION})",
+            snapshot.version
+        );
+    }
+
+    let mut report = ImportReport::default();
+    let tx = conn.unchecked_transaction()?;
+
+    for (i, word) in snapshot.words.iter().enumerate() {
+        if word.word.trim().is_empty() {
+            report.reject(format!("word {}", i + 1), "missing word");
+            continue;
+        }
+
+        queries::upsert_word(&tx, word)?;
+        report.imported += 1;
+    }
+
+    // Version 1 snapshots carry no history, so the existing one is kept
+    if snapshot.version >= 2 {
+        let mut ids = HashMap::new();
+        for word in &snapshot.words {
+            if let Some(found) = queries::fetch_word(&tx, &word.word)? {
+                queries::delete_reviews(&tx, found.id)?;
+                ids.insert(word.word.as_str(), found.id);
+            }
+        }
+        for review in &snapshot.reviews {
+            if let Some(id) = ids.get(review.word.as_str()) {
+                queries::insert_review(&tx, *id, review.reviewed_at, review.correct)?;
+            }
+        }
+    }
+
+    for day in &snapshot.daily_activity {
+        queries::set_daily_activity(&tx, &day.day, day.reviews, day.new_words)?;
+    }
+    for (key, value) in &snapshot.app_state {
+        queries::set_app_state(&tx, key, *value)?;
+    }
+    for (key, value) in &snapshot.settings {
+        queries::set_setting(&tx, key, value)?;
+    }
+
+    tx.commit()?;
+    Ok(report)
+}
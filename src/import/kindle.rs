@@ -0,0 +1,231 @@
+// Kindle Vocabulary Builder import
+// Reads looked-up words and their usage sentences from a Kindle's vocab.db
+
+use super::ImportReport;
+use crate::db::queries;
+use anyhow::{Result, bail};
+use rusqlite::{Connection, OpenFlags};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Put every word in this group instead of one group per book
+    pub group: Option<i32>,
+}
+
+struct Lookup {
+    word: String,
+    book: String,
+    usage: String,
+}
+
+/// Words are imported by their stem (the dictionary form) with an empty
+/// definition, keeping the sentence from the first lookup as the example.
+pub fn import_file<W: Write>(
+    conn: &Connection,
+    path: &str,
+    options: &Options,
+    output: &mut W,
+) -> Result<ImportReport> {
+    let kindle = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let lookups = fetch_lookups(&kindle)
+        .map_err(|e| anyhow::anyhow!("{path} is not a Kindle vocabulary database: {e}"))?;
+
+    let groups: HashMap<String, i32> = match options.group {
+        Some(group) => lookups.iter().map(|l| (l.book.clone(), group)).collect(),
+        None => {
+            let books: BTreeSet<&String> = lookups.iter().map(|l| &l.book).collect();
+            let first_group = queries::max_group_id(conn)?.unwrap_or(0) + 1;
+            let groups: HashMap<String, i32> =
+                books.into_iter().cloned().zip(first_group..).collect();
+
+            let mut mapping: Vec<_> = groups.iter().collect();
+            mapping.sort_by_key(|(_, group)| **group);
+            for (book, group) in mapping {
+                writeln!(output, "Book \"{book}\" -> Group {group}")?;
+            }
+            groups
+        }
+    };
+
+    let mut report = ImportReport::default();
+    let tx = conn.unchecked_transaction()?;
+
+    for lookup in &lookups {
+        if !queries::insert_word(&tx, &lookup.word, "", groups[&lookup.book], "")? {
+            report.reject(&lookup.word, "already exists");
+            continue;
+        }
+        if !lookup.usage.is_empty() {
+            queries::set_example(&tx, &lookup.word, &lookup.usage)?;
+        }
+        report.imported += 1;
+    }
+
+    tx.commit()?;
+    Ok(report)
+}
+
+/// One entry per distinct word, from its earliest lookup.
+fn fetch_lookups(kindle: &Connection) -> Result<Vec<Lookup>> {
+    let mut stmt = kindle.prepare(
+        "SELECT w.word, w.stem, COALESCE(b.title, ''), COALESCE(l.usage, '')
+         FROM LOOKUPS l
+         JOIN WORDS w ON w.id = l.word_key
+         LEFT JOIN BOOK_INFO b ON b.id = l.book_key
+         ORDER BY l.timestamp",
+    )?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let word: String = row.get(0)?;
+            let stem: Option<String> = row.get(1)?;
+            Ok((
+                word,
+                stem,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut seen = BTreeSet::new();
+    let mut lookups = Vec::new();
+    for (word, stem, book, usage) in rows {
+        let word = stem
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(word)
+            .trim()
+            .to_lowercase();
+        if word.is_empty() || !seen.insert(word.clone()) {
+            continue;
+        }
+
+        lookups.push(Lookup {
+            word,
+            book: if book.trim().is_empty() {
+                "Unknown book".to_string()
+            } else {
+                book.trim().to_string()
+            },
+            usage: usage.split_whitespace().collect::<Vec<_>>().join(" "),
+        });
+    }
+
+    if lookups.is_empty() {
+        bail!("no looked-up words found");
+    }
+
+    Ok(lookups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+    use tempfile::NamedTempFile;
+
+    fn kindle_db() -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        let conn = Connection::open(file.path()).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE WORDS (id TEXT PRIMARY KEY, word TEXT, stem TEXT, lang TEXT,
+                                category INTEGER DEFAULT 0, timestamp INTEGER DEFAULT 0);
+            CREATE TABLE LOOKUPS (id TEXT PRIMARY KEY, word_key TEXT, book_key TEXT,
+                                  dict_key TEXT, pos TEXT, usage TEXT, timestamp INTEGER DEFAULT 0);
+            CREATE TABLE BOOK_INFO (id TEXT PRIMARY KEY, asin TEXT, guid TEXT, lang TEXT,
+                                    title TEXT, authors TEXT);
+            INSERT INTO BOOK_INFO VALUES ('b1', '', '', 'en', 'Moby Dick', 'Melville');
+            INSERT INTO BOOK_INFO VALUES ('b2', '', '', 'en', 'Emma', 'Austen');
+            INSERT INTO WORDS VALUES ('en:portentous', 'portentous', 'portentous', 'en', 0, 1);
+            INSERT INTO WORDS VALUES ('en:abounded', 'abounded', 'abound', 'en', 0, 2);
+            INSERT INTO WORDS VALUES ('en:abounds', 'abounds', 'abound', 'en', 0, 3);
+            INSERT INTO LOOKUPS VALUES ('l1', 'en:portentous', 'b1', '', '',
+                                        'A  portentous   silence fell.', 10);
+            INSERT INTO LOOKUPS VALUES ('l2', 'en:abounded', 'b2', '', '', 'Joy abounded.', 20);
+            INSERT INTO LOOKUPS VALUES ('l3', 'en:abounds', 'b1', '', '', 'Fish abounds.', 30);
+            "#,
+        )
+        .unwrap();
+        file
+    }
+
+    fn word_row(conn: &Connection, word: &str) -> (String, i32, Option<String>) {
+        conn.query_row(
+            "SELECT definition, group_id, example FROM words WHERE word=?1",
+            [word],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_one_group_per_book() {
+        let conn = init_db(":memory:").unwrap();
+        queries::insert_word(&conn, "existing", "x", 2, "").unwrap();
+        let file = kindle_db();
+
+        let mut output = Vec::new();
+        let report = import_file(
+            &conn,
+            file.path().to_str().unwrap(),
+            &Options::default(),
+            &mut output,
+        )
+        .unwrap();
+
+        // Both "abound" lookups collapse into their shared stem
+        assert_eq!(report.imported, 2);
+        assert_eq!(
+            word_row(&conn, "portentous"),
+            (String::new(), 4, Some("A portentous silence fell.".into()))
+        );
+        assert_eq!(
+            word_row(&conn, "abound"),
+            (String::new(), 3, Some("Joy abounded.".into()))
+        );
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains("\"Emma\" -> Group 3")
+        );
+    }
+
+    #[test]
+    fn test_chosen_group() {
+        let conn = init_db(":memory:").unwrap();
+        let file = kindle_db();
+
+        let options = Options { group: Some(9) };
+        import_file(
+            &conn,
+            file.path().to_str().unwrap(),
+            &options,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(word_row(&conn, "abound").1, 9);
+        assert_eq!(word_row(&conn, "portentous").1, 9);
+    }
+
+    #[test]
+    fn test_not_a_kindle_database() {
+        let conn = init_db(":memory:").unwrap();
+        let file = NamedTempFile::new().unwrap();
+        Connection::open(file.path())
+            .unwrap()
+            .execute_batch("CREATE TABLE other (id INTEGER);")
+            .unwrap();
+
+        let result = import_file(
+            &conn,
+            file.path().to_str().unwrap(),
+            &Options::default(),
+            &mut Vec::new(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,161 @@
+pub mod apkg;
+pub mod delimited;
+pub mod json;
+pub mod kindle;
+
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Csv,
+    Tsv,
+    Apkg,
+    Json,
+}
+
+impl Format {
+    /// Guess the format from a file extension, for when `--format` is omitted.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let ext = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(Format::Csv),
+            "tsv" | "tab" => Some(Format::Tsv),
+            "apkg" | "colpkg" => Some(Format::Apkg),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// Reject `--group` for formats that bring their own groups: an Anki
+    /// package gets one new group per deck and a snapshot keeps its groups.
+    pub fn check_group(self, group: Option<i32>) -> Result<()> {
+        match (self, group) {
+            (Format::Apkg, Some(_)) => {
+                bail!("--group does not apply to .apkg, each deck gets its own group")
+            }
+            (Format::Json, Some(_)) => {
+                bail!("--group does not apply to JSON snapshots, they keep their groups")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A source row that was not imported, with the reason why. `location` is
+/// whatever identifies the row in the source: a line number, a note id...
+#[derive(Debug)]
+pub struct Rejected {
+    pub location: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub rejected: Vec<Rejected>,
+}
+
+impl ImportReport {
+    pub fn reject(&mut self, location: impl ToString, reason: impl Into<String>) {
+        self.rejected.push(Rejected {
+            location: location.to_string(),
+            reason: reason.into(),
+        });
+    }
+
+    pub fn print(&self, path: &str) {
+        for rejected in &self.rejected {
+            println!(
+                "{path}:{}: rejected: {}",
+                rejected.location, rejected.reason
+            );
+        }
+        println!(
+            "Imported {} words, rejected {} rows.",
+            self.imported,
+            self.rejected.len()
+        );
+    }
+}
+
+/// Turn the HTML stored by flashcard apps and e-books into plain text:
+/// line-breaking tags become newlines, other tags are dropped and the common
+/// entities are decoded.
+pub fn strip_html(input: &str) -> String {
+    let mut text = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let tag = rest[start + 1..start + end]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if matches!(
+            tag.as_str(),
+            "br" | "div" | "p" | "li" | "tr" | "h1" | "h2" | "h3"
+        ) {
+            text.push('\n');
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_html() {
+        assert_eq!(
+            strip_html("<b>strict</b> &amp; stern<br>lacking&nbsp;luxury<div>harsh</div>"),
+            "strict & stern\nlacking luxury\nharsh"
+        );
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path("words.CSV"), Some(Format::Csv));
+        assert_eq!(Format::from_path("words.tsv"), Some(Format::Tsv));
+        assert_eq!(Format::from_path("deck.apkg"), Some(Format::Apkg));
+        assert_eq!(Format::from_path("words"), None);
+    }
+
+    #[test]
+    fn test_group_only_for_delimited_formats() {
+        assert!(Format::Csv.check_group(Some(2)).is_ok());
+        assert!(Format::Tsv.check_group(Some(2)).is_ok());
+        assert!(Format::Apkg.check_group(None).is_ok());
+        assert!(Format::Json.check_group(None).is_ok());
+
+        let err = Format::Apkg.check_group(Some(2)).unwrap_err();
+        assert!(err.to_string().contains("--group"));
+        assert!(Format::Json.check_group(Some(2)).is_err());
+    }
+}
@@ -1,13 +1,30 @@
 mod audio;
+mod config;
 mod core;
 mod db;
+mod dict;
+mod doctor;
+mod export;
+mod extract;
+mod import;
+mod quiz;
+mod reset;
 mod seed;
 mod ui;
+mod words;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow, bail};
 use clap::{Parser, Subcommand};
-use db::init_db;
-use seed::seed_from_file;
+use core::goal;
+use db::{init_db, queries};
+use dict::{Dictionaries, Dictionary};
+use import::Format;
+use seed::{check_file, seed_from_file};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+const DB_PATH: &str = "vocab.db";
 
 #[derive(Parser)]
 #[command(name = "vocabulator")]
@@ -18,19 +35,682 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    Seed { file: String },
+    Seed {
+        file: String,
+        /// Parse and report problems without writing to the database
+        #[arg(long)]
+        check: bool,
+    },
+    /// Import words from a CSV/TSV file, an Anki package or a JSON export
+    Import {
+        #[arg(required_unless_present = "kindle")]
+        file: Option<String>,
+        /// Import looked-up words from a Kindle Vocabulary Builder database
+        #[arg(long, value_name = "PATH", conflicts_with = "file")]
+        kindle: Option<String>,
+        /// File format, guessed from the extension when omitted
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+        /// Column holding the word (header name or 1-based number)
+        #[arg(long, default_value = "word")]
+        word_col: String,
+        /// Column holding the definition
+        #[arg(long, default_value = "definition")]
+        def_col: String,
+        /// Column holding the group number
+        #[arg(long)]
+        group_col: Option<String>,
+        /// Column holding tags
+        #[arg(long)]
+        tags_col: Option<String>,
+        /// Group for rows without a group column (default 1), or for all
+        /// Kindle words instead of one group per book; not for apkg or json
+        #[arg(long)]
+        group: Option<i32>,
+        /// Anki note field holding the word (name or 1-based number)
+        #[arg(long)]
+        word_field: Option<String>,
+        /// Anki note field holding the definition
+        #[arg(long)]
+        def_field: Option<String>,
+        /// Carry Anki review counts over into word stats
+        #[arg(long)]
+        with_reviews: bool,
+    },
+    /// Export words and progress
+    Export {
+        #[arg(long, value_enum, default_value = "csv")]
+        format: export::Format,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Only words in this group
+        #[arg(long)]
+        group: Option<i32>,
+        /// Only marked words
+        #[arg(long)]
+        marked: bool,
+        /// Only the words offered by "Revise Weak"
+        #[arg(long)]
+        weak: bool,
+    },
+    /// Manage offline StarDict/dictd dictionaries
+    Dict {
+        #[command(subcommand)]
+        action: DictAction,
+    },
+    /// Look a word up in the registered dictionaries
+    Define {
+        word: String,
+        /// Add the word with its first definition
+        #[arg(long)]
+        add: bool,
+        /// Group for the added word
+        #[arg(long, default_value_t = 1, requires = "add")]
+        group: i32,
+    },
+    /// Find uncommon words in a text or EPUB file and pick which to add
+    Extract {
+        file: String,
+        /// Group to add words to (default: a new group)
+        #[arg(long)]
+        group: Option<i32>,
+        /// Show at most this many candidates
+        #[arg(long, default_value_t = 200)]
+        limit: usize,
+        /// Print the ranked candidates instead of opening the checklist
+        #[arg(long)]
+        print: bool,
+    },
+    /// Run a quiz over plain stdin/stdout, without the full-screen UI
+    Quiz {
+        #[arg(long, value_enum, default_value = "test")]
+        mode: quiz::Mode,
+        /// Number of words to ask
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+        /// Words in this group (default: continue the saved group)
+        #[arg(long, conflicts_with_all = ["marked", "weak"])]
+        group: Option<i32>,
+        /// Marked words
+        #[arg(long, conflicts_with = "weak")]
+        marked: bool,
+        /// The words offered by "Revise Weak"
+        #[arg(long)]
+        weak: bool,
+    },
+    /// Show progress: totals, per-group accuracy and the weakest words
+    Stats {
+        /// Only words in this group
+        #[arg(long)]
+        group: Option<i32>,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+        /// Print a calendar of daily reviews over the past year
+        #[arg(long, conflicts_with_all = ["group", "json"])]
+        heatmap: bool,
+    },
+    /// List words
+    List {
+        /// Only words in this group
+        #[arg(long)]
+        group: Option<i32>,
+        /// Only marked words
+        #[arg(long)]
+        marked: bool,
+        /// Only the words offered by "Revise Weak"
+        #[arg(long)]
+        weak: bool,
+        #[arg(long, value_enum, default_value = "group")]
+        sort: words::Sort,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show everything about one word
+    Show {
+        word: String,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Find words whose text, definition, tags or example contain TEXT
+    Search {
+        text: String,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a word
+    Add {
+        word: String,
+        /// Looked up in your dictionaries when left out
+        definition: Option<String>,
+        #[arg(long, default_value_t = 1)]
+        group: i32,
+    },
+    /// Change a word's text, definition, tags or example
+    Edit {
+        word: String,
+        /// New text for the word itself
+        #[arg(long, value_name = "WORD")]
+        rename: Option<String>,
+        #[arg(long)]
+        definition: Option<String>,
+        #[arg(long)]
+        tags: Option<String>,
+        /// Usage sentence; pass "" to clear it
+        #[arg(long)]
+        example: Option<String>,
+    },
+    /// Delete a word and its stats
+    Remove { word: String },
+    /// Mark a word for review
+    Mark { word: String },
+    /// Clear a word's mark
+    Unmark { word: String },
+    /// Move a word to another group
+    Move {
+        word: String,
+        #[arg(long)]
+        group: i32,
+    },
+    /// Reset review stats, marks, the saved position or the tutorial
+    #[command(group(
+        clap::ArgGroup::new("what")
+            .args(["stats", "marks", "position", "tutorial"])
+            .required(true)
+            .multiple(true)
+    ))]
+    Reset {
+        /// Times seen, correct answers and last-seen time
+        #[arg(long)]
+        stats: bool,
+        /// Review marks
+        #[arg(long)]
+        marks: bool,
+        /// The saved "Continue Learning" position
+        #[arg(long, conflicts_with = "word")]
+        position: bool,
+        /// Offer the tutorial again
+        #[arg(long, conflicts_with_all = ["group", "word"])]
+        tutorial: bool,
+        /// Only words in this group
+        #[arg(long, conflicts_with = "word")]
+        group: Option<i32>,
+        /// Only this word
+        #[arg(long)]
+        word: Option<String>,
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Copy the database to PATH (default: vocab-<date>-<time>.db)
+    Backup { path: Option<String> },
+    /// Replace the database with a backup; the current one is snapshotted first
+    Restore { path: String },
+    /// Show the daily goal and streaks, or change the goal
+    Goal {
+        /// Aim for N graded answers a day
+        #[arg(long, value_name = "N", conflicts_with = "new_words")]
+        reviews: Option<u32>,
+        /// Aim for N words answered for the first time a day
+        #[arg(long, value_name = "N")]
+        new_words: Option<u32>,
+    },
+    /// Check the database for corruption and inconsistent progress
+    Doctor {
+        /// Repair what can be repaired, after taking a snapshot
+        #[arg(long)]
+        fix: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DictAction {
+    /// Register a dictionary (any of its .ifo/.idx/.index/.dict files)
+    Add { path: String },
+    /// Show registered dictionaries
+    List,
+    /// Unregister a dictionary
+    Remove { path: String },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let conn = init_db("vocab.db")?;
 
     match cli.command {
-        Some(Commands::Seed { file }) => {
-            seed_from_file(&conn, &file)?;
+        Some(Commands::Seed { file, check: true }) => {
+            let parsed = check_file(&file)?;
+            println!(
+                "{} entries, {} error(s), {} warning(s)",
+                parsed.entries.len(),
+                parsed.error_count(),
+                parsed.warning_count()
+            );
+            if parsed.error_count() > 0 {
+                bail!("{file} failed validation");
+            }
+        }
+        Some(Commands::Seed { file, check: false }) => {
+            let conn = init_db(DB_PATH)?;
+            let filled = seed_from_file(&conn, &file)?;
+            if filled > 0 {
+                println!("Filled {filled} empty definitions from dictionaries.");
+            }
             println!("Database seeded successfully.");
         }
+        Some(Commands::Import {
+            kindle: Some(path),
+            group,
+            ..
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let options = import::kindle::Options { group };
+            let report = import::kindle::import_file(&conn, &path, &options, &mut io::stdout())?;
+            report.print(&path);
+        }
+        Some(Commands::Import {
+            file: Some(file),
+            format,
+            word_col,
+            def_col,
+            group_col,
+            tags_col,
+            group,
+            word_field,
+            def_field,
+            with_reviews,
+            kindle: None,
+        }) => {
+            let format = format
+                .or_else(|| Format::from_path(&file))
+                .ok_or_else(|| anyhow!("cannot guess the format of {file}, pass --format"))?;
+            format.check_group(group)?;
+
+            let conn = init_db(DB_PATH)?;
+            let report = match format {
+                Format::Csv | Format::Tsv => {
+                    let options = import::delimited::Options {
+                        delimiter: if format == Format::Csv { b',' } else { b'\t' },
+                        columns: import::delimited::ColumnMap {
+                            word: word_col,
+                            definition: def_col,
+                            group: group_col,
+                            tags: tags_col,
+                        },
+                        default_group: group.unwrap_or(1),
+                    };
+                    import::delimited::import_file(&conn, &file, &options)?
+                }
+                Format::Apkg => {
+                    let options = import::apkg::Options {
+                        word_field,
+                        def_field,
+                        with_reviews,
+                    };
+                    import::apkg::import_file(
+                        &conn,
+                        &file,
+                        &options,
+                        &mut io::stdin().lock(),
+                        &mut io::stdout(),
+                    )?
+                }
+                Format::Json => import::json::import_file(&conn, &file)?,
+            };
+            report.print(&file);
+        }
+        Some(Commands::Import { .. }) => unreachable!("clap requires a file or --kindle"),
+        Some(Commands::Export {
+            format,
+            output,
+            group,
+            marked,
+            weak,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let filter = export::Filter {
+                group,
+                marked,
+                weak,
+            };
+
+            match output {
+                Some(path) => {
+                    let mut file = io::BufWriter::new(File::create(&path)?);
+                    let count = export::export(&conn, format, &filter, &mut file)?;
+                    file.flush()?;
+                    println!("Exported {count} words to {path}.");
+                }
+                None => {
+                    export::export(&conn, format, &filter, &mut io::stdout().lock())?;
+                }
+            }
+        }
+        Some(Commands::Dict { action }) => {
+            let conn = init_db(DB_PATH)?;
+            match action {
+                DictAction::Add { path } => {
+                    let dict = Dictionary::open(&path)?;
+                    if let Some(existing) = dict::find_registered(&conn, &path)? {
+                        bail!("{path} is already registered as {existing}");
+                    }
+                    let path = dict::absolute(&path).to_string_lossy().into_owned();
+                    queries::add_dictionary(&conn, &path)?;
+                    println!("Added dictionary \"{}\".", dict.name());
+                }
+                DictAction::List => {
+                    let paths = queries::fetch_dictionaries(&conn)?;
+                    if paths.is_empty() {
+                        println!("No dictionaries registered. Add one with `dict add <path>`.");
+                    }
+                    for path in paths {
+                        match Dictionary::open(&path) {
+                            Ok(dict) => println!("{}\t{path}", dict.name()),
+                            Err(e) => println!("(unreadable)\t{path}: {e}"),
+                        }
+                    }
+                }
+                DictAction::Remove { path } => {
+                    let Some(registered) = dict::find_registered(&conn, &path)? else {
+                        bail!("{path} is not registered");
+                    };
+                    queries::remove_dictionary(&conn, &registered)?;
+                    println!("Removed {registered}.");
+                }
+            }
+        }
+        Some(Commands::Define { word, add, group }) => {
+            let conn = init_db(DB_PATH)?;
+            let dictionaries = Dictionaries::load(&conn)?;
+            if dictionaries.is_empty() {
+                bail!("no dictionaries registered, add one with `dict add <path>`");
+            }
+
+            let definitions = dictionaries.lookup(&word);
+            if definitions.is_empty() {
+                bail!("\"{word}\" not found in any dictionary");
+            }
+
+            let mut current = None;
+            for definition in &definitions {
+                if current != Some(&definition.dictionary) {
+                    println!("== {} ==", definition.dictionary);
+                    current = Some(&definition.dictionary);
+                }
+                println!("{}\n", definition.text);
+            }
+
+            if add {
+                // Dictionary headwords are looked up in lower case, so
+                // store the word that way too
+                let word = words::add(
+                    &conn,
+                    &word.to_lowercase(),
+                    Some(&definitions[0].text),
+                    group,
+                    &dictionaries,
+                )?;
+                println!("Added \"{}\" to Group {}.", word.word, word.group_id);
+            }
+        }
+        Some(Commands::Extract {
+            file,
+            group,
+            limit,
+            print,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let dictionaries = Dictionaries::load(&conn)?;
+            let text = extract::read_source(&file)?;
+
+            let mut candidates = extract::find_candidates(&conn, &text, &dictionaries)?;
+            candidates.truncate(limit);
+            if candidates.is_empty() {
+                println!("No new words found in {file}.");
+                return Ok(());
+            }
+
+            if print {
+                for candidate in &candidates {
+                    println!(
+                        "{}\t{}\t{}",
+                        candidate.word, candidate.count, candidate.sentence
+                    );
+                }
+                return Ok(());
+            }
+
+            let group = match group {
+                Some(group) => group,
+                None => queries::max_group_id(&conn)?.unwrap_or(0) + 1,
+            };
+            // The checklist uses the same keys and colours as the app
+            let config = config::Config::load(config::CONFIG_PATH)?;
+            let settings = core::settings::Settings::load(&conn)?;
+            let keymap = ui::keymap::Keymap::build(&settings.keys, &config)?;
+            let theme = ui::theme::Theme::load(&settings.theme, &config)?;
+            match ui::checklist::run(&candidates, group, &keymap, &theme)? {
+                Some((chosen, group)) => {
+                    let added = extract::add_candidates(&conn, &chosen, group, &dictionaries)?;
+                    println!("Added {added} words to Group {group}.");
+                }
+                None => println!("Nothing added."),
+            }
+        }
+        Some(Commands::Quiz {
+            mode,
+            count,
+            group,
+            marked,
+            weak,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let filter = quiz::Filter {
+                group,
+                marked,
+                weak,
+            };
+            let mut session = quiz::start_session(&conn, &filter)?;
+            quiz::run(
+                &conn,
+                &mut session,
+                mode,
+                count,
+                &mut io::stdin().lock(),
+                &mut io::stdout(),
+            )?;
+        }
+        Some(Commands::Stats { heatmap: true, .. }) => {
+            let conn = init_db(DB_PATH)?;
+            let heatmap =
+                core::heatmap::Heatmap::compute(&conn, chrono::Local::now().date_naive())?;
+            heatmap.write_text(&mut io::stdout().lock())?;
+        }
+        Some(Commands::Stats { group, json, .. }) => {
+            let conn = init_db(DB_PATH)?;
+            let report = core::stats::Report::compute(&conn, group)?;
+            if json {
+                serde_json::to_writer_pretty(io::stdout().lock(), &report)?;
+                println!();
+            } else {
+                report.write_text(&mut io::stdout().lock())?;
+            }
+        }
+        Some(Commands::List {
+            group,
+            marked,
+            weak,
+            sort,
+            json,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let filter = export::Filter {
+                group,
+                marked,
+                weak,
+            };
+            let list = words::list(&conn, &filter, sort)?;
+            if json {
+                serde_json::to_writer_pretty(io::stdout().lock(), &list)?;
+                println!();
+            } else {
+                words::write_table(&list, &mut io::stdout().lock())?;
+            }
+        }
+        Some(Commands::Show { word, json }) => {
+            let conn = init_db(DB_PATH)?;
+            let word = words::show(&conn, &word)?;
+            if json {
+                serde_json::to_writer_pretty(io::stdout().lock(), &word)?;
+                println!();
+            } else {
+                words::write_details(&word, &mut io::stdout().lock())?;
+            }
+        }
+        Some(Commands::Search { text, json }) => {
+            let conn = init_db(DB_PATH)?;
+            let found = queries::search_words(&conn, &text)?;
+            if json {
+                serde_json::to_writer_pretty(io::stdout().lock(), &found)?;
+                println!();
+            } else if found.is_empty() {
+                println!("No words match \"{text}\".");
+            } else {
+                words::write_table(&found, &mut io::stdout().lock())?;
+            }
+        }
+        Some(Commands::Add {
+            word,
+            definition,
+            group,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let dictionaries = match definition {
+                Some(_) => Dictionaries::default(),
+                None => Dictionaries::load(&conn)?,
+            };
+            let word = words::add(&conn, &word, definition.as_deref(), group, &dictionaries)?;
+            println!("Added \"{}\" to Group {}.", word.word, word.group_id);
+        }
+        Some(Commands::Edit {
+            word,
+            rename,
+            definition,
+            tags,
+            example,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let changes = words::Edit {
+                word: rename,
+                definition,
+                tags,
+                example,
+            };
+            let word = words::edit(&conn, &word, &changes)?;
+            words::write_details(&word, &mut io::stdout().lock())?;
+        }
+        Some(Commands::Remove { word }) => {
+            let conn = init_db(DB_PATH)?;
+            let word = words::remove(&conn, &word)?;
+            println!("Removed \"{}\" from Group {}.", word.word, word.group_id);
+        }
+        Some(Commands::Mark { word }) => {
+            let conn = init_db(DB_PATH)?;
+            let word = words::set_marked(&conn, &word, true)?;
+            println!("Marked \"{}\".", word.word);
+        }
+        Some(Commands::Unmark { word }) => {
+            let conn = init_db(DB_PATH)?;
+            let word = words::set_marked(&conn, &word, false)?;
+            println!("Unmarked \"{}\".", word.word);
+        }
+        Some(Commands::Move { word, group }) => {
+            let conn = init_db(DB_PATH)?;
+            let word = words::move_to(&conn, &word, group)?;
+            println!("Moved \"{}\" to Group {}.", word.word, word.group_id);
+        }
+        Some(Commands::Reset {
+            stats,
+            marks,
+            position,
+            tutorial,
+            group,
+            word,
+            yes,
+        }) => {
+            let conn = init_db(DB_PATH)?;
+            let options = reset::Options {
+                stats,
+                marks,
+                position,
+                tutorial,
+                group,
+                word,
+            };
+            reset::run(
+                &conn,
+                &options,
+                yes,
+                &mut io::stdin().lock(),
+                &mut io::stdout(),
+            )?;
+        }
+        Some(Commands::Backup { path }) => {
+            let conn = init_db(DB_PATH)?;
+            let path = path.unwrap_or_else(db::backup::default_name);
+            db::backup::backup(&conn, Path::new(&path))?;
+            println!("Backed up to {path}.");
+        }
+        Some(Commands::Restore { path }) => {
+            let mut conn = init_db(DB_PATH)?;
+            if let Some(saved) = db::backup::restore(&mut conn, Path::new(&path))? {
+                println!("Saved the previous database to {}.", saved.display());
+            }
+            drop(conn);
+            // Bring a backup from an older version up to date
+            init_db(DB_PATH)?;
+            println!("Restored {path}.");
+        }
+        Some(Commands::Goal { reviews, new_words }) => {
+            let conn = init_db(DB_PATH)?;
+            let change = match (reviews, new_words) {
+                (Some(target), _) => Some((goal::Kind::Reviews, target)),
+                (_, Some(target)) => Some((goal::Kind::NewWords, target)),
+                _ => None,
+            };
+            if let Some((kind, target)) = change {
+                if target == 0 {
+                    bail!("the daily goal must be at least 1");
+                }
+                goal::Goal { kind, target }.save(&conn)?;
+            }
+
+            let status = goal::Status::compute(&conn, chrono::Local::now().date_naive())?;
+            println!(
+                "Daily goal: {} {}",
+                status.goal.target,
+                status.goal.kind.label()
+            );
+            println!("Today:      {}/{}", status.today, status.goal.target);
+            println!(
+                "Streak:     {} (longest {})",
+                status.current_streak, status.longest_streak
+            );
+        }
+        Some(Commands::Doctor { fix }) => {
+            let conn = init_db(DB_PATH)?;
+            let left = doctor::run(&conn, fix, &mut io::stdout())?;
+            if left > 0 && !fix {
+                bail!("{left} problem(s) found, run `doctor --fix` to repair them");
+            } else if left > 0 {
+                bail!("{left} problem(s) could not be fixed");
+            }
+        }
         None => {
+            init_db(DB_PATH)?;
             ui::run::run()?;
         }
     }
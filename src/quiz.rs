@@ -0,0 +1,303 @@
+// Line-oriented quiz
+// Runs practice or test drills over plain stdin/stdout for scripts and SSH
+
+use crate::core::session::{self, Session, Type};
+use crate::core::settings::{Settings, Strictness};
+use crate::core::{goal, progress};
+use crate::db::queries;
+use crate::ui::app::Screen;
+use anyhow::{Result, bail};
+use chrono::Local;
+use clap::ValueEnum;
+use rusqlite::Connection;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Type the word for the shown definition
+    Test,
+    /// See the word, reveal the definition and grade yourself
+    Practice,
+}
+
+impl Mode {
+    fn screen(self) -> Screen {
+        match self {
+            Mode::Test => Screen::Test,
+            Mode::Practice => Screen::Practice,
+        }
+    }
+}
+
+/// Which words to quiz; without a filter the saved group position is
+/// continued, like "Continue Learning" in the TUI.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Filter {
+    pub group: Option<i32>,
+    pub marked: bool,
+    pub weak: bool,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub asked: usize,
+    pub correct: usize,
+}
+
+pub fn start_session(conn: &Connection, filter: &Filter) -> Result<Session> {
+    let session = match filter {
+        Filter { marked: true, .. } => session::start_session(conn, Type::Marked)?.0,
+        Filter { weak: true, .. } => session::start_session(conn, Type::Weak)?.0,
+        Filter {
+            group: Some(group), ..
+        } => Session::new(
+            queries::fetch_words_by_group(conn, *group)?,
+            0,
+            Type::Custom,
+        ),
+        _ => session::start_session(conn, Type::Group)?.0,
+    };
+
+    if session.words.is_empty() {
+        bail!("no words to quiz");
+    }
+    Ok(session)
+}
+
+/// Ask up to `count` words, recording each answer. Stops early on `q` or
+/// end of input.
+pub fn run<R: BufRead, W: Write>(
+    conn: &Connection,
+    session: &mut Session,
+    mode: Mode,
+    count: usize,
+    input: &mut R,
+    output: &mut W,
+) -> Result<Summary> {
+    let mut summary = Summary::default();
+    let total = count.min(session.words.len());
+    let today = Local::now().date_naive();
+    let mut goal_reached = goal::Status::compute(conn, today)?.reached();
+    let strictness = Settings::load(conn)?.strictness;
+
+    while summary.asked < total {
+        let number = summary.asked + 1;
+        let correct = match mode {
+            Mode::Test => ask_test(session, strictness, number, total, input, output)?,
+            Mode::Practice => ask_practice(session, number, total, input, output)?,
+        };
+        let Some(correct) = correct else {
+            break;
+        };
+
+        progress::update_word_stats(conn, session.current_mut(), correct)?;
+        progress::record_review(conn, session.current(), correct)?;
+        summary.asked += 1;
+        summary.correct += correct as usize;
+
+        let status = goal::Status::compute(conn, today)?;
+        if status.reached() && !goal_reached {
+            writeln!(output, "Daily goal reached! {}", status.summary())?;
+            goal_reached = true;
+        }
+
+        session.advance();
+        if session.session_type == Type::Group {
+            progress::save_progress(
+                conn,
+                (mode.screen(), session.current().group_id, session.index),
+            )?;
+        }
+        writeln!(output)?;
+    }
+
+    writeln!(output, "Score: {}/{}", summary.correct, summary.asked)?;
+    Ok(summary)
+}
+
+fn ask_test<R: BufRead, W: Write>(
+    session: &Session,
+    strictness: Strictness,
+    number: usize,
+    total: usize,
+    input: &mut R,
+    output: &mut W,
+) -> Result<Option<bool>> {
+    let word = session.current();
+    writeln!(output, "[{number}/{total}] Group {}", word.group_id)?;
+    writeln!(output, "{}", word.definition)?;
+
+    let Some(answer) = prompt(input, output, "> ")? else {
+        return Ok(None);
+    };
+    let correct = strictness.accepts(&answer, &word.word);
+    if correct {
+        writeln!(output, "Correct!")?;
+    } else {
+        writeln!(output, "Wrong, it was: {}", word.word)?;
+    }
+    Ok(Some(correct))
+}
+
+fn ask_practice<R: BufRead, W: Write>(
+    session: &Session,
+    number: usize,
+    total: usize,
+    input: &mut R,
+    output: &mut W,
+) -> Result<Option<bool>> {
+    let word = session.current();
+    writeln!(output, "[{number}/{total}] {}", word.word)?;
+    if prompt(input, output, "Press Enter to reveal ")?.is_none() {
+        return Ok(None);
+    }
+
+    writeln!(output, "{}", word.definition)?;
+    if let Some(example) = &word.example {
+        writeln!(output, "\"{example}\"")?;
+    }
+
+    loop {
+        match prompt(input, output, "Did you know it? [y/n] ")?.as_deref() {
+            None => return Ok(None),
+            Some("y" | "Y") => return Ok(Some(true)),
+            Some("n" | "N") => return Ok(Some(false)),
+            Some(_) => {}
+        }
+    }
+}
+
+/// Print `label` and read one trimmed line; `None` on end of input or `q`.
+fn prompt<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    label: &str,
+) -> Result<Option<String>> {
+    write!(output, "{label}")?;
+    output.flush()?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let line = line.trim();
+    if line == "q" {
+        return Ok(None);
+    }
+    Ok(Some(line.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn setup() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        queries::insert_word(&conn, "abound", "be plentiful", 1, "").unwrap();
+        queries::insert_word(&conn, "austere", "strict and stern", 1, "").unwrap();
+        queries::insert_word(&conn, "belie", "contradict", 2, "").unwrap();
+        conn
+    }
+
+    fn stats(conn: &Connection, word: &str) -> (u8, u8) {
+        conn.query_row(
+            "SELECT times_seen, success_count FROM words WHERE word=?1",
+            [word],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mode_records_answers() {
+        let conn = setup();
+        let mut session = start_session(&conn, &Filter::default()).unwrap();
+
+        let mut output = Vec::new();
+        let summary = run(
+            &conn,
+            &mut session,
+            Mode::Test,
+            5,
+            &mut "ABOUND\nstern\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary,
+            Summary {
+                asked: 2,
+                correct: 1
+            }
+        );
+        assert_eq!(stats(&conn, "abound"), (1, 1));
+        assert_eq!(stats(&conn, "austere"), (1, 0));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Wrong, it was: austere"));
+        assert!(output.ends_with("Score: 1/2\n"));
+    }
+
+    #[test]
+    fn test_practice_mode_reprompts_and_saves_position() {
+        let conn = setup();
+        let mut session = start_session(&conn, &Filter::default()).unwrap();
+
+        let summary = run(
+            &conn,
+            &mut session,
+            Mode::Practice,
+            1,
+            &mut "\nmaybe\ny\n".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary,
+            Summary {
+                asked: 1,
+                correct: 1
+            }
+        );
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Practice, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_quit_stops_early() {
+        let conn = setup();
+        let filter = Filter {
+            group: Some(2),
+            ..Default::default()
+        };
+        let mut session = start_session(&conn, &filter).unwrap();
+
+        let summary = run(
+            &conn,
+            &mut session,
+            Mode::Test,
+            3,
+            &mut "q\n".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(summary.asked, 0);
+        assert_eq!(stats(&conn, "belie"), (0, 0));
+    }
+
+    #[test]
+    fn test_empty_selection_is_an_error() {
+        let conn = setup();
+        let filter = Filter {
+            marked: true,
+            ..Default::default()
+        };
+
+        assert!(start_session(&conn, &filter).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+// Progress reset
+// Clears review stats, marks, the saved position or the tutorial flag
+
+use crate::db::{backup, models::Word, queries, queries::Scope};
+use anyhow::{Result, bail};
+use rusqlite::Connection;
+use std::io::{BufRead, Write};
+
+/// What to reset, and for which words. Stats and marks default to every
+/// word; `position` with a group only rewinds that group.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub stats: bool,
+    pub marks: bool,
+    pub position: bool,
+    pub tutorial: bool,
+    pub group: Option<i32>,
+    pub word: Option<String>,
+}
+
+/// Describe the reset, ask for confirmation unless `yes`, then apply it in
+/// one transaction. Returns `false` if the user declined.
+pub fn run<R: BufRead, W: Write>(
+    conn: &Connection,
+    options: &Options,
+    yes: bool,
+    input: &mut R,
+    output: &mut W,
+) -> Result<bool> {
+    if !(options.stats || options.marks || options.position || options.tutorial) {
+        bail!("nothing to reset, pass --stats, --marks, --position or --tutorial");
+    }
+    if options.position && options.word.is_some() {
+        bail!("--position resets a whole group, it cannot be used with --word");
+    }
+
+    let (scope, label, words) = resolve(conn, options)?;
+
+    writeln!(output, "This will reset:")?;
+    if options.stats {
+        let seen = words.iter().filter(|w| w.times_seen > 0).count();
+        writeln!(output, "  - review stats of {label} ({seen} reviewed)")?;
+    }
+    if options.marks {
+        let marked = words.iter().filter(|w| w.marked).count();
+        writeln!(output, "  - marks on {label} ({marked} marked)")?;
+    }
+    if options.position {
+        match options.group {
+            Some(group) => writeln!(output, "  - the saved position in Group {group}")?,
+            None => writeln!(output, "  - the saved position, back to Group 1")?,
+        }
+    }
+    if options.tutorial {
+        writeln!(output, "  - the tutorial, so it is offered again")?;
+    }
+
+    if !yes && !confirm(input, output)? {
+        writeln!(output, "Cancelled.")?;
+        return Ok(false);
+    }
+
+    if let Some(path) = backup::snapshot(conn, "reset")? {
+        writeln!(output, "Saved a snapshot to {}", path.display())?;
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    if options.stats {
+        queries::clear_stats(&tx, scope)?;
+    }
+    if options.marks {
+        queries::clear_marks(&tx, scope)?;
+    }
+    if options.position {
+        match options.group {
+            Some(group) => {
+                let (screen, saved_group, _) = queries::fetch_progress(&tx)?;
+                if saved_group == group {
+                    queries::save_progress(&tx, (screen, group, 0))?;
+                }
+            }
+            None => queries::clear_progress(&tx)?,
+        }
+    }
+    if options.tutorial {
+        queries::set_tutorial_completed(&tx, false)?;
+    }
+    tx.commit()?;
+
+    writeln!(output, "Reset done.")?;
+    Ok(true)
+}
+
+/// The scope for stats and marks, a description of it and the words in it.
+fn resolve(conn: &Connection, options: &Options) -> Result<(Scope, String, Vec<Word>)> {
+    if let Some(word) = &options.word {
+        let Some(found) = queries::fetch_word(conn, word)? else {
+            bail!("\"{word}\" is not in the word list");
+        };
+        return Ok((
+            Scope::Word(found.id),
+            format!("\"{}\"", found.word),
+            vec![found],
+        ));
+    }
+
+    if let Some(group) = options.group {
+        let words = queries::fetch_words_by_group(conn, group)?;
+        if words.is_empty() {
+            bail!("Group {group} has no words");
+        }
+        let label = format!("{} words in Group {group}", words.len());
+        return Ok((Scope::Group(group), label, words));
+    }
+
+    let words = queries::fetch_all_words(conn)?;
+    Ok((Scope::All, format!("all {} words", words.len()), words))
+}
+
+fn confirm<R: BufRead, W: Write>(input: &mut R, output: &mut W) -> Result<bool> {
+    write!(output, "Continue? [y/N] ")?;
+    output.flush()?;
+
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+    use crate::ui::app::Screen;
+
+    fn setup() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        for (word, group) in [("abound", 1), ("austere", 1), ("belie", 2)] {
+            queries::insert_word(&conn, word, "", group, "").unwrap();
+            queries::set_review_counts(&conn, word, 3, 2).unwrap();
+        }
+        conn.execute("UPDATE words SET marked=1, last_seen=100", [])
+            .unwrap();
+        conn
+    }
+
+    fn state(conn: &Connection, word: &str) -> (u8, u8, bool, Option<i32>) {
+        conn.query_row(
+            "SELECT times_seen, success_count, marked, last_seen FROM words WHERE word=?1",
+            [word],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_group_scope_leaves_other_groups() {
+        let conn = setup();
+        let options = Options {
+            stats: true,
+            group: Some(1),
+            ..Default::default()
+        };
+
+        assert!(run(&conn, &options, true, &mut "".as_bytes(), &mut Vec::new()).unwrap());
+
+        assert_eq!(state(&conn, "abound"), (0, 0, true, None));
+        assert_eq!(state(&conn, "belie"), (3, 2, true, Some(100)));
+    }
+
+    #[test]
+    fn test_declining_changes_nothing() {
+        let conn = setup();
+        let options = Options {
+            stats: true,
+            marks: true,
+            ..Default::default()
+        };
+
+        let mut output = Vec::new();
+        let applied = run(&conn, &options, false, &mut "n\n".as_bytes(), &mut output).unwrap();
+
+        assert!(!applied);
+        assert_eq!(state(&conn, "austere"), (3, 2, true, Some(100)));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("review stats of all 3 words (3 reviewed)"));
+        assert!(output.ends_with("Cancelled.\n"));
+    }
+
+    #[test]
+    fn test_word_marks_and_tutorial() {
+        let conn = setup();
+        queries::set_tutorial_completed(&conn, true).unwrap();
+        let options = Options {
+            marks: true,
+            word: Some("BELIE".into()),
+            ..Default::default()
+        };
+
+        run(
+            &conn,
+            &options,
+            false,
+            &mut "y\n".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        let tutorial = Options {
+            tutorial: true,
+            ..Default::default()
+        };
+        run(&conn, &tutorial, true, &mut "".as_bytes(), &mut Vec::new()).unwrap();
+
+        assert_eq!(state(&conn, "belie"), (3, 2, false, Some(100)));
+        assert!(state(&conn, "abound").2);
+        assert!(!queries::get_tutorial_completed(&conn).unwrap());
+    }
+
+    #[test]
+    fn test_position_only_rewinds_the_saved_group() {
+        let conn = setup();
+        queries::save_progress(&conn, (Screen::Test, 2, 5)).unwrap();
+        let position = |group| Options {
+            position: true,
+            group,
+            ..Default::default()
+        };
+
+        run(
+            &conn,
+            &position(Some(1)),
+            true,
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Test, 2, 5)
+        );
+
+        run(
+            &conn,
+            &position(Some(2)),
+            true,
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Test, 2, 0)
+        );
+
+        run(
+            &conn,
+            &position(None),
+            true,
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            queries::fetch_progress(&conn).unwrap(),
+            (Screen::Practice, 1, 0)
+        );
+    }
+
+    #[test]
+    fn test_stats_reset_clears_goal_progress() {
+        use crate::core::goal::{Goal, Kind, Status};
+        use chrono::Local;
+
+        let conn = setup();
+        Goal {
+            kind: Kind::Reviews,
+            target: 2,
+        }
+        .save(&conn)
+        .unwrap();
+        let now = Local::now();
+        let day = now.format("%Y-%m-%d").to_string();
+        let id = |word| queries::fetch_word(&conn, word).unwrap().unwrap().id;
+        for (word, new) in [("abound", true), ("abound", false), ("belie", true)] {
+            queries::insert_review(&conn, id(word), now.timestamp(), true).unwrap();
+            queries::record_activity(&conn, &day, new).unwrap();
+        }
+        let status = |conn| Status::compute(conn, now.date_naive()).unwrap();
+        assert_eq!((status(&conn).today, status(&conn).current_streak), (3, 1));
+
+        let stats = |group| Options {
+            stats: true,
+            group,
+            ..Default::default()
+        };
+        run(
+            &conn,
+            &stats(Some(1)),
+            true,
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        // Only belie's review and its first sighting are left
+        assert_eq!(
+            queries::fetch_daily_activity(&conn).unwrap(),
+            vec![(day, 1, 1)]
+        );
+        assert_eq!((status(&conn).today, status(&conn).current_streak), (1, 0));
+
+        run(
+            &conn,
+            &stats(None),
+            true,
+            &mut "".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert!(queries::fetch_daily_activity(&conn).unwrap().is_empty());
+        assert_eq!(status(&conn).longest_streak, 0);
+    }
+
+    #[test]
+    fn test_unknown_scope_is_an_error() {
+        let conn = setup();
+        let options = Options {
+            stats: true,
+            group: Some(9),
+            ..Default::default()
+        };
+
+        assert!(run(&conn, &options, true, &mut "".as_bytes(), &mut Vec::new()).is_err());
+        assert!(
+            run(
+                &conn,
+                &Options::default(),
+                true,
+                &mut "".as_bytes(),
+                &mut Vec::new()
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,95 +1,338 @@
-use anyhow::{Result, anyhow};
+use crate::db::backup;
+use crate::dict::Dictionaries;
+use anyhow::{Result, bail};
 use rusqlite::{Connection, params};
+use std::collections::HashMap;
 use std::fs;
 
-pub fn seed_from_file(conn: &Connection, path: &str) -> Result<()> {
+/// Explicit separator between word and definition, for entries whose word
+/// contains spaces.
+const ENTRY_DELIMITER: &str = "::";
+
+/// How serious a problem found while parsing a seed file is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A single problem in a seed file, tied to the (1-based) line it was found on.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+    pub snippet: String,
+}
+
+impl Diagnostic {
+    pub fn render(&self, path: &str) -> String {
+        format!(
+            "{path}:{}: {}: {}\n    | {}",
+            self.line,
+            self.severity.label(),
+            self.message,
+            self.snippet
+        )
+    }
+}
+
+/// A word parsed from a seed file, ready to be inserted.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub word: String,
+    pub definition: String,
+    pub group_id: i32,
+    pub line: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedSeed {
+    pub entries: Vec<Entry>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ParsedSeed {
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+}
+
+/// Empty definitions are filled from the registered dictionaries; returns how
+/// many were filled.
+pub fn seed_from_file(conn: &Connection, path: &str) -> Result<usize> {
     let content = fs::read_to_string(path)?;
-    let mut group_id: i32 = 0;
+    let parsed = parse_seed(&content);
+
+    if parsed.error_count() > 0 {
+        let report: Vec<String> = parsed
+            .diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.render(path))
+            .collect();
+        bail!(
+            "{} error(s) in seed file, nothing was written:\n{}",
+            parsed.error_count(),
+            report.join("\n")
+        );
+    }
 
-    let mut current_word: Option<String> = None;
-    let mut current_definition = String::new();
+    let dictionaries = if parsed.entries.iter().any(|e| e.definition.is_empty()) {
+        Dictionaries::load(conn)?
+    } else {
+        Dictionaries::default()
+    };
 
-    for raw_line in content.lines() {
-        let line = raw_line.trim();
+    backup::snapshot(conn, "seed")?;
 
-        if line.is_empty() {
-            continue;
+    let mut filled = 0;
+    let tx = conn.unchecked_transaction()?;
+    for entry in &parsed.entries {
+        let mut definition = entry.definition.clone();
+        if definition.is_empty()
+            && let Some(found) = dictionaries.first_definition(&entry.word)
+        {
+            definition = found;
+            filled += 1;
         }
 
-        if line.starts_with("Group") {
-            flush_current(conn, &mut current_word, &mut current_definition, group_id)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO words (word, group_id, definition)
+             VALUES (?1, ?2, ?3)",
+            params![entry.word, entry.group_id, definition],
+        )?;
+    }
+    tx.commit()?;
 
-            let id = line
-                .split_whitespace()
-                .last()
-                .ok_or_else(|| anyhow!("Invalid group line: {line}"))?;
+    Ok(filled)
+}
+
+/// Parse a seed file without touching the database and print every problem
+/// found. Returns the parse result so the caller can decide the exit status.
+pub fn check_file(path: &str) -> Result<ParsedSeed> {
+    let content = fs::read_to_string(path)?;
+    let parsed = parse_seed(&content);
 
-            group_id = id.parse::<i32>()?;
+    for diagnostic in &parsed.diagnostics {
+        println!("{}", diagnostic.render(path));
+    }
+
+    Ok(parsed)
+}
+
+pub fn parse_seed(content: &str) -> ParsedSeed {
+    let mut parsed = ParsedSeed::default();
+    let mut group_id: Option<i32> = None;
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    let mut current: Option<Entry> = None;
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw_line.trim();
+
+        if line.is_empty() {
             continue;
         }
 
-        // Continuation definition line (starts with digit.)
-        if line
-            .chars()
-            .next()
-            .map(|c| c.is_ascii_digit())
-            .unwrap_or(false)
-            && line.contains('.')
-        {
-            let cleaned = line
-                .split_once('.')
-                .map(|(_, rest)| rest.trim())
-                .unwrap_or(line);
-
-            if !current_definition.is_empty() {
-                current_definition.push('\n');
+        if is_group_header(line) {
+            flush_current(&mut parsed, &mut current, &mut seen);
+
+            match parse_group_header(line) {
+                Some(id) => group_id = Some(id),
+                None => {
+                    parsed.diagnostics.push(Diagnostic {
+                        line: line_no,
+                        severity: Severity::Error,
+                        message: "malformed group header, expected `Group <number>`".into(),
+                        snippet: line.to_string(),
+                    });
+                    // Words up to the next valid header have no usable group.
+                    group_id = None;
+                }
             }
-            current_definition.push_str(cleaned);
             continue;
         }
 
-        // Continuation if line starts with '('
-        if line.starts_with('(') {
-            if !current_definition.is_empty() {
-                current_definition.push('\n');
+        if let Some(cleaned) = continuation_text(line) {
+            match current.as_mut() {
+                Some(entry) => {
+                    if !entry.definition.is_empty() {
+                        entry.definition.push('\n');
+                    }
+                    entry.definition.push_str(cleaned);
+                }
+                None => parsed.diagnostics.push(Diagnostic {
+                    line: line_no,
+                    severity: Severity::Error,
+                    message: "continuation line does not follow a word".into(),
+                    snippet: line.to_string(),
+                }),
             }
-            current_definition.push_str(line.trim());
             continue;
         }
 
         // New word — flush previous
-        flush_current(conn, &mut current_word, &mut current_definition, group_id)?;
+        flush_current(&mut parsed, &mut current, &mut seen);
+
+        let (word, definition_part) = split_entry(line);
 
-        let mut parts = line.splitn(2, ' ');
-        let word = parts.next().unwrap().to_string();
-        let definition_part = parts.next().unwrap_or("").trim();
+        if word.is_empty() {
+            parsed.diagnostics.push(Diagnostic {
+                line: line_no,
+                severity: Severity::Error,
+                message: "entry has no word before the delimiter".into(),
+                snippet: line.to_string(),
+            });
+            continue;
+        }
 
-        current_word = Some(word);
-        current_definition = normalize_inline_definitions(definition_part);
+        let group_id = match group_id {
+            Some(id) => id,
+            None => {
+                parsed.diagnostics.push(Diagnostic {
+                    line: line_no,
+                    severity: Severity::Error,
+                    message: format!(
+                        "word \"{word}\" has no group, add a `Group <number>` header above it"
+                    ),
+                    snippet: line.to_string(),
+                });
+                0
+            }
+        };
+
+        current = Some(Entry {
+            word,
+            definition: normalize_inline_definitions(definition_part),
+            group_id,
+            line: line_no,
+        });
     }
 
     // flush last entry
-    flush_current(conn, &mut current_word, &mut current_definition, group_id)?;
+    flush_current(&mut parsed, &mut current, &mut seen);
+
+    parsed.diagnostics.sort_by_key(|d| d.line);
+    parsed
+}
+
+fn is_group_header(line: &str) -> bool {
+    line.split_whitespace().next() == Some("Group")
+}
+
+fn parse_group_header(line: &str) -> Option<i32> {
+    let mut parts = line.split_whitespace().skip(1);
+    let id = parts.next()?.parse::<i32>().ok()?;
+    match parts.next() {
+        Some(_) => None,
+        None => Some(id),
+    }
+}
+
+/// Split an entry line into word and definition. The delimiter is detected
+/// per line: `word :: definition` and `word<TAB>definition` allow multi-word
+/// entries such as "ad hoc", otherwise the first space separates the two.
+fn split_entry(line: &str) -> (String, &str) {
+    let (word, definition) = if let Some((word, definition)) = line.split_once(ENTRY_DELIMITER) {
+        (word, definition)
+    } else if let Some((word, definition)) = line.split_once('\t') {
+        (word, definition)
+    } else {
+        line.split_once(' ').unwrap_or((line, ""))
+    };
+
+    let word = word.split_whitespace().collect::<Vec<_>>().join(" ");
+    (word, definition.trim())
+}
+
+/// Returns the definition text of a continuation line (`2. ...` or `(...)`),
+/// or `None` if the line starts a new word.
+fn continuation_text(line: &str) -> Option<&str> {
+    // An explicit delimiter always starts a new entry
+    if line.contains(ENTRY_DELIMITER) {
+        return None;
+    }
+
+    // Continuation definition line (starts with digit.)
+    if line
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or(false)
+        && line.contains('.')
+    {
+        return Some(
+            line.split_once('.')
+                .map(|(_, rest)| rest.trim())
+                .unwrap_or(line),
+        );
+    }
+
+    // Continuation if line starts with '('
+    if line.starts_with('(') {
+        return Some(line);
+    }
 
-    Ok(())
+    None
 }
 
 fn flush_current(
-    conn: &Connection,
-    current_word: &mut Option<String>,
-    current_definition: &mut String,
-    group_id: i32,
-) -> Result<()> {
-    if let Some(word) = current_word.take() {
-        conn.execute(
-            "INSERT OR IGNORE INTO words (word, group_id, definition)
-             VALUES (?1, ?2, ?3)",
-            params![word, group_id, current_definition.trim()],
-        )?;
+    parsed: &mut ParsedSeed,
+    current: &mut Option<Entry>,
+    seen: &mut HashMap<String, usize>,
+) {
+    let Some(mut entry) = current.take() else {
+        return;
+    };
+
+    entry.definition = entry.definition.trim().to_string();
+
+    if entry.definition.is_empty() {
+        parsed.diagnostics.push(Diagnostic {
+            line: entry.line,
+            severity: Severity::Warning,
+            message: format!("word \"{}\" has an empty definition", entry.word),
+            snippet: entry.word.clone(),
+        });
     }
 
-    current_definition.clear();
-    Ok(())
+    if let Some(first) = seen.get(&entry.word) {
+        parsed.diagnostics.push(Diagnostic {
+            line: entry.line,
+            severity: Severity::Warning,
+            message: format!(
+                "duplicate word \"{}\" (first defined on line {first}), this entry is ignored",
+                entry.word
+            ),
+            snippet: entry.word.clone(),
+        });
+        return;
+    }
+
+    seen.insert(entry.word.clone(), entry.line);
+    parsed.entries.push(entry);
 }
 
 fn normalize_inline_definitions(input: &str) -> String {
@@ -256,4 +499,149 @@ adulterate damage the quality of; corrupt
 
         assert_eq!(group_id, 42);
     }
+
+    #[test]
+    fn test_malformed_group_reports_line() {
+        let conn = init_db(":memory:").unwrap();
+
+        let data = "Group 1\nabound plentiful\nGroup two\nbelie mask\n";
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "{}", data).unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let err = seed_from_file(&conn, path).unwrap_err().to_string();
+        assert!(err.contains(&format!("{path}:3: error")), "{err}");
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM words", [], |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_check_missing_group_header() {
+        let parsed = parse_seed("abound plentiful\nGroup 1\nbelie mask\n");
+
+        assert_eq!(parsed.error_count(), 1);
+        assert_eq!(parsed.diagnostics[0].line, 1);
+        assert_eq!(parsed.diagnostics[0].snippet, "abound plentiful");
+    }
+
+    #[test]
+    fn test_check_duplicate_word() {
+        let parsed = parse_seed("Group 1\nabound plentiful\nGroup 2\nabound again\n");
+
+        assert_eq!(parsed.error_count(), 0);
+        assert_eq!(parsed.warning_count(), 1);
+        assert_eq!(parsed.diagnostics[0].line, 4);
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.entries[0].definition, "plentiful");
+    }
+
+    #[test]
+    fn test_check_empty_definition() {
+        let parsed = parse_seed("Group 1\nabound\nbelie mask\n");
+
+        assert_eq!(parsed.warning_count(), 1);
+        assert_eq!(parsed.diagnostics[0].severity, Severity::Warning);
+        assert_eq!(parsed.diagnostics[0].line, 2);
+    }
+
+    #[test]
+    fn test_check_dangling_continuation() {
+        let parsed = parse_seed("Group 1\n2. lacking luxury\nausterity frugality\n");
+
+        assert_eq!(parsed.error_count(), 1);
+        assert_eq!(parsed.diagnostics[0].line, 2);
+        assert_eq!(parsed.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_phrase_with_explicit_delimiter() {
+        let parsed = parse_seed("Group 1\nad hoc :: created for a particular purpose\n");
+
+        assert_eq!(parsed.entries[0].word, "ad hoc");
+        assert_eq!(
+            parsed.entries[0].definition,
+            "created for a particular purpose"
+        );
+    }
+
+    #[test]
+    fn test_phrase_with_tab_delimiter() {
+        let parsed = parse_seed("Group 1\nbona fide\tgenuine; real\n");
+
+        assert_eq!(parsed.entries[0].word, "bona fide");
+        assert_eq!(parsed.entries[0].definition, "genuine; real");
+    }
+
+    #[test]
+    fn test_mixed_delimiters_with_continuations() {
+        let data = r#"
+Group 1
+laissez-faire attitude :: 1. a policy of non-interference 2. indifference
+3. tolerance of others' choices
+abound be present in large quantities
+amenable	(of a person) receptive to change
+(of a thing) responsive to
+"#;
+        let parsed = parse_seed(data);
+
+        assert_eq!(parsed.error_count(), 0);
+        assert_eq!(parsed.entries.len(), 3);
+        assert_eq!(parsed.entries[0].word, "laissez-faire attitude");
+        assert_eq!(
+            parsed.entries[0].definition,
+            "a policy of non-interference\nindifference\ntolerance of others' choices"
+        );
+        assert_eq!(parsed.entries[1].word, "abound");
+        assert_eq!(parsed.entries[2].word, "amenable");
+        assert_eq!(
+            parsed.entries[2].definition,
+            "(of a person) receptive to change\n(of a thing) responsive to"
+        );
+    }
+
+    #[test]
+    fn test_phrase_whitespace_is_normalized() {
+        let parsed = parse_seed("Group 1\n  de   facto   ::  in fact\n");
+
+        assert_eq!(parsed.entries[0].word, "de facto");
+        assert_eq!(parsed.entries[0].definition, "in fact");
+    }
+
+    #[test]
+    fn test_missing_word_before_delimiter() {
+        let parsed = parse_seed("Group 1\n:: orphan definition\n");
+
+        assert_eq!(parsed.error_count(), 1);
+        assert!(parsed.entries.is_empty());
+    }
+
+    #[test]
+    fn test_empty_definitions_filled_from_dictionary() {
+        let conn = init_db(":memory:").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("wn.index"), "laconic\tA\tP\n").unwrap();
+        std::fs::write(dir.path().join("wn.dict"), "using few words").unwrap();
+        crate::db::queries::add_dictionary(&conn, dir.path().join("wn.index").to_str().unwrap())
+            .unwrap();
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "Group 1\nlaconic\nterse brief\n").unwrap();
+
+        let filled = seed_from_file(&conn, file.path().to_str().unwrap()).unwrap();
+
+        let definition: String = conn
+            .query_row(
+                "SELECT definition FROM words WHERE word='laconic'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(filled, 1);
+        assert_eq!(definition, "using few words");
+    }
 }
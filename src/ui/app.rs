@@ -1,19 +1,31 @@
+use crate::config::Config;
+use crate::core::goal;
 use crate::core::session::{Session, Type};
+use crate::core::settings::Settings;
+use crate::core::stats::{Dashboard, GroupSummary};
 use crate::core::tutorial::TutorialState;
+use crate::ui::keymap::Keymap;
+use crate::ui::theme::Theme;
 use rusqlite::Connection;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Screen {
     Menu,
     Practice,
     Test,
     TutorialPrompt,
     Tutorial,
+    Stats,
+    Groups,
+    Settings,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum MenuAction {
     Session(Type),
+    Groups,
+    Stats,
+    Settings,
     RestartTutorial,
     Exit,
 }
@@ -22,6 +34,9 @@ impl MenuAction {
     pub fn label(&self) -> &'static str {
         match self {
             MenuAction::Session(t) => t.label(),
+            MenuAction::Groups => "Choose Group",
+            MenuAction::Stats => "Statistics",
+            MenuAction::Settings => "Settings",
             MenuAction::RestartTutorial => "Restart Tutorial",
             MenuAction::Exit => "Exit",
         }
@@ -38,17 +53,36 @@ pub struct App {
     pub session: Option<Session>,
     pub error: Option<String>,
     pub tutorial_state: Option<TutorialState>,
+    pub dashboard: Option<Dashboard>,
+    /// Rows of the group picker and the highlighted one
+    pub groups: Vec<GroupSummary>,
+    pub group_selected: usize,
+    pub settings: Settings,
+    pub settings_selected: usize,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// The config file, kept to rebuild the keymap and theme when they change
+    pub config: Config,
+    /// Whether the key help overlay is open
+    pub show_help: bool,
+    /// Today's progress towards the daily goal, shown in the menu header
+    pub goal: Option<goal::Status>,
 }
 
 impl App {
     pub fn new(conn: Connection) -> Self {
+        let goal = goal::Status::compute(&conn, chrono::Local::now().date_naive()).ok();
+        let settings = Settings::load(&conn).unwrap_or_default();
         Self {
             conn,
             current_screen: Screen::Menu,
             menu_items: vec![
                 MenuAction::Session(Type::Group),
+                MenuAction::Groups,
                 MenuAction::Session(Type::Marked),
                 MenuAction::Session(Type::Weak),
+                MenuAction::Stats,
+                MenuAction::Settings,
                 MenuAction::RestartTutorial,
                 MenuAction::Exit,
             ],
@@ -57,6 +91,16 @@ impl App {
             session: None,
             error: None,
             tutorial_state: None,
+            dashboard: None,
+            groups: Vec::new(),
+            group_selected: 0,
+            settings,
+            settings_selected: 0,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            config: Config::default(),
+            show_help: false,
+            goal,
         }
     }
 
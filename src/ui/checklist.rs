@@ -0,0 +1,365 @@
+// Checklist screen
+// Lets the user tick which extracted words to add and pick their group
+
+use crate::extract::Candidate;
+use crate::ui::keymap::{Action, Context, Keymap};
+use crate::ui::layout::row_at;
+use crate::ui::screens::help;
+use crate::ui::terminal::{init_terminal, restore_terminal};
+use crate::ui::theme::Theme;
+use anyhow::Result;
+use crossterm::event::{
+    self, Event, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+#[derive(Debug)]
+pub struct Checklist<'a> {
+    pub candidates: &'a [Candidate],
+    pub checked: Vec<bool>,
+    pub selected: usize,
+    pub group: i32,
+    /// `Some(true)` once confirmed, `Some(false)` if cancelled
+    pub done: Option<bool>,
+    pub show_help: bool,
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+}
+
+impl<'a> Checklist<'a> {
+    pub fn new(
+        candidates: &'a [Candidate],
+        group: i32,
+        keymap: &'a Keymap,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            candidates,
+            checked: vec![false; candidates.len()],
+            selected: 0,
+            group,
+            done: None,
+            show_help: false,
+            keymap,
+            theme,
+        }
+    }
+
+    pub fn chosen(&self) -> Vec<&'a Candidate> {
+        self.candidates
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .map(|(candidate, _)| candidate)
+            .collect()
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) {
+        if self.show_help {
+            // Any key closes the overlay
+            self.show_help = false;
+            return;
+        }
+
+        let len = self.candidates.len();
+        match self.keymap.action(Context::Checklist, &key) {
+            Some(Action::Down) if len > 0 => {
+                self.selected = (self.selected + 1) % len;
+            }
+            Some(Action::Up) if len > 0 => {
+                self.selected = (self.selected + len - 1) % len;
+            }
+            Some(Action::Toggle) if len > 0 => {
+                self.checked[self.selected] = !self.checked[self.selected];
+            }
+            Some(Action::ToggleAll) => {
+                let all = self.checked.iter().all(|c| *c);
+                self.checked.iter_mut().for_each(|c| *c = !all);
+            }
+            Some(Action::Right) => self.group += 1,
+            Some(Action::Left) => self.group = (self.group - 1).max(1),
+            Some(Action::Confirm) => self.done = Some(true),
+            Some(Action::Back) => self.done = Some(false),
+            Some(Action::Help) => self.show_help = true,
+            _ => {}
+        }
+    }
+
+    /// A click ticks or unticks the word under it, the wheel moves the
+    /// selection. `area` is the whole terminal.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) {
+        if self.show_help {
+            // A click closes the overlay, like any key
+            if let MouseEventKind::Down(_) = mouse.kind {
+                self.show_help = false;
+            }
+            return;
+        }
+
+        let len = self.candidates.len();
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let list = layout(area)[0].inner(&Margin::new(1, 1));
+                if let Some(row) = row_at(list, len, self.selected, mouse.column, mouse.row) {
+                    self.selected = row;
+                    self.checked[row] = !self.checked[row];
+                }
+            }
+            MouseEventKind::ScrollDown if len > 0 => {
+                self.selected = (self.selected + 1).min(len - 1)
+            }
+            MouseEventKind::ScrollUp => self.selected = self.selected.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame) {
+        let layout = layout(frame.size());
+
+        let items: Vec<ListItem> = self
+            .candidates
+            .iter()
+            .zip(&self.checked)
+            .map(|(candidate, checked)| {
+                ListItem::new(format!(
+                    "[{}] {} ({})",
+                    if *checked { "x" } else { " " },
+                    candidate.word,
+                    candidate.count
+                ))
+            })
+            .collect();
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected));
+
+        let title = format!(
+            " {} words found, {} selected, adding to Group {} ",
+            self.candidates.len(),
+            self.checked.iter().filter(|c| **c).count(),
+            self.group
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, layout[0], &mut state);
+
+        let sentence = self
+            .candidates
+            .get(self.selected)
+            .map(|c| c.sentence.as_str())
+            .unwrap_or("");
+        frame.render_widget(
+            Paragraph::new(sentence)
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title(" Example ")),
+            layout[1],
+        );
+
+        let label = |action| self.keymap.label(Context::Checklist, action);
+        let mut spans = Vec::new();
+        for (key, name) in [
+            (label(Action::Toggle), "Toggle"),
+            (label(Action::ToggleAll), "All"),
+            (
+                format!("{}/{}", label(Action::Right), label(Action::Left)),
+                "Group",
+            ),
+            (label(Action::Confirm), "Add"),
+            (label(Action::Back), "Cancel"),
+            (label(Action::Help), "Help"),
+        ] {
+            if !spans.is_empty() {
+                spans.push(Span::raw("   "));
+            }
+            spans.push(Span::styled(
+                format!("[{key}]"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(format!(" {name}")));
+        }
+        let keys = Line::from(spans);
+        frame.render_widget(
+            Paragraph::new(keys).block(Block::default().borders(Borders::ALL)),
+            layout[2],
+        );
+
+        if self.show_help {
+            help::render_keys(frame, self.keymap, Context::Checklist, self.theme);
+        }
+    }
+}
+
+/// Words, example sentence and keys.
+fn layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(5),    // Words
+            Constraint::Length(5), // Sentence
+            Constraint::Length(3), // Keys
+        ])
+        .split(area)
+}
+
+/// Show the checklist until the user confirms or cancels. Returns the
+/// chosen candidates and group, or `None` when cancelled.
+pub fn run<'a>(
+    candidates: &'a [Candidate],
+    group: i32,
+    keymap: &'a Keymap,
+    theme: &'a Theme,
+) -> Result<Option<(Vec<&'a Candidate>, i32)>> {
+    let mut terminal = init_terminal()?;
+    let mut checklist = Checklist::new(candidates, group, keymap, theme);
+
+    let result = loop {
+        if let Err(e) = terminal.draw(|f| checklist.render(f)) {
+            break Err(e.into());
+        }
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => checklist.handle_event(key),
+            Ok(Event::Mouse(mouse)) => match terminal.size() {
+                Ok(area) => checklist.handle_mouse(mouse, area),
+                Err(e) => break Err(e.into()),
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+        match checklist.done {
+            Some(true) => break Ok(Some((checklist.chosen(), checklist.group))),
+            Some(false) => break Ok(None),
+            None => {}
+        }
+    };
+
+    restore_terminal(terminal)?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn candidates() -> Vec<Candidate> {
+        ["ubiquitous", "abound", "whale"]
+            .iter()
+            .map(|w| Candidate {
+                word: w.to_string(),
+                count: 1,
+                sentence: String::new(),
+            })
+            .collect()
+    }
+
+    fn press(checklist: &mut Checklist, code: KeyCode) {
+        checklist.handle_event(KeyEvent::new(code, KeyModifiers::empty()));
+    }
+
+    fn mouse(checklist: &mut Checklist, kind: MouseEventKind, row: u16) {
+        let event = MouseEvent {
+            kind,
+            column: 5,
+            row,
+            modifiers: KeyModifiers::empty(),
+        };
+        checklist.handle_mouse(event, Rect::new(0, 0, 80, 24));
+    }
+
+    #[test]
+    fn test_toggle_and_confirm() {
+        let candidates = candidates();
+        let (keymap, theme) = (Keymap::default(), Theme::default());
+        let mut checklist = Checklist::new(&candidates, 3, &keymap, &theme);
+
+        press(&mut checklist, KeyCode::Char(' '));
+        press(&mut checklist, KeyCode::Down);
+        press(&mut checklist, KeyCode::Down);
+        press(&mut checklist, KeyCode::Char(' '));
+        press(&mut checklist, KeyCode::Char('+'));
+        press(&mut checklist, KeyCode::Enter);
+
+        let chosen: Vec<&str> = checklist.chosen().iter().map(|c| c.word.as_str()).collect();
+        assert_eq!(chosen, vec!["ubiquitous", "whale"]);
+        assert_eq!(checklist.group, 4);
+        assert_eq!(checklist.done, Some(true));
+    }
+
+    #[test]
+    fn test_select_all_toggles() {
+        let candidates = candidates();
+        let (keymap, theme) = (Keymap::default(), Theme::default());
+        let mut checklist = Checklist::new(&candidates, 1, &keymap, &theme);
+
+        press(&mut checklist, KeyCode::Char('a'));
+        assert_eq!(checklist.chosen().len(), 3);
+        press(&mut checklist, KeyCode::Char('a'));
+        assert!(checklist.chosen().is_empty());
+    }
+
+    #[test]
+    fn test_navigation_wraps_and_group_stays_positive() {
+        let candidates = candidates();
+        let (keymap, theme) = (Keymap::default(), Theme::default());
+        let mut checklist = Checklist::new(&candidates, 1, &keymap, &theme);
+
+        press(&mut checklist, KeyCode::Up);
+        press(&mut checklist, KeyCode::Char('-'));
+        press(&mut checklist, KeyCode::Esc);
+
+        assert_eq!(checklist.selected, 2);
+        assert_eq!(checklist.group, 1);
+        assert_eq!(checklist.done, Some(false));
+    }
+
+    #[test]
+    fn test_click_ticks_word() {
+        let candidates = candidates();
+        let (keymap, theme) = (Keymap::default(), Theme::default());
+        let mut checklist = Checklist::new(&candidates, 1, &keymap, &theme);
+        let click = MouseEventKind::Down(MouseButton::Left);
+
+        // Margin on row 0, border on row 1, words from row 2
+        mouse(&mut checklist, click, 4);
+        assert_eq!(checklist.selected, 2);
+        mouse(&mut checklist, click, 2);
+        mouse(&mut checklist, click, 10);
+        let chosen: Vec<&str> = checklist.chosen().iter().map(|c| c.word.as_str()).collect();
+        assert_eq!(chosen, vec!["ubiquitous", "whale"]);
+        assert_eq!(checklist.selected, 0);
+
+        mouse(&mut checklist, MouseEventKind::ScrollDown, 10);
+        assert_eq!(checklist.selected, 1);
+        assert_eq!(checklist.done, None);
+    }
+
+    #[test]
+    fn test_configured_keys() {
+        let candidates = candidates();
+        let config = Config::parse("test.conf", "[keys]\nchecklist.toggle = x\n").unwrap();
+        let keymap = Keymap::build("default", &config).unwrap();
+        let theme = Theme::default();
+        let mut checklist = Checklist::new(&candidates, 1, &keymap, &theme);
+
+        press(&mut checklist, KeyCode::Char(' '));
+        assert!(checklist.chosen().is_empty());
+        press(&mut checklist, KeyCode::Char('x'));
+        assert_eq!(checklist.chosen().len(), 1);
+
+        press(&mut checklist, KeyCode::Char('?'));
+        assert!(checklist.show_help);
+        press(&mut checklist, KeyCode::Enter);
+        assert!(!checklist.show_help);
+        assert_eq!(checklist.done, None);
+    }
+}
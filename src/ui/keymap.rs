@@ -0,0 +1,701 @@
+// Key bindings
+// Maps keys to actions for each screen; the handlers and the help overlay both read it
+
+use crate::config::Config;
+use crate::ui::app::Screen;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+/// Built-in key layouts that the config file can adjust further.
+pub const PRESETS: [&str; 3] = ["default", "vim", "arrows-only"];
+
+/// Where keys are read: one of the app's screens, or the checklist that
+/// `extract` shows outside the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    Screen(Screen),
+    Checklist,
+}
+
+impl From<Screen> for Context {
+    fn from(screen: Screen) -> Self {
+        Context::Screen(screen)
+    }
+}
+
+/// Every context, in the order used in the config file documentation.
+const CONTEXTS: [Context; 9] = [
+    Context::Screen(Screen::Menu),
+    Context::Screen(Screen::Practice),
+    Context::Screen(Screen::Test),
+    Context::Screen(Screen::TutorialPrompt),
+    Context::Screen(Screen::Tutorial),
+    Context::Screen(Screen::Stats),
+    Context::Screen(Screen::Groups),
+    Context::Screen(Screen::Settings),
+    Context::Checklist,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+    Help,
+    Show,
+    Correct,
+    Wrong,
+    Mark,
+    Next,
+    Insert,
+    Submit,
+    LeaveInsert,
+    DeleteChar,
+    Practice,
+    Test,
+    Refresh,
+    TogglePage,
+    ScrollUp,
+    ScrollDown,
+    Toggle,
+    ToggleAll,
+    Confirm,
+    Cancel,
+}
+
+impl Action {
+    const ALL: [Action; 26] = [
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Select,
+        Action::Back,
+        Action::Help,
+        Action::Show,
+        Action::Correct,
+        Action::Wrong,
+        Action::Mark,
+        Action::Next,
+        Action::Insert,
+        Action::Submit,
+        Action::LeaveInsert,
+        Action::DeleteChar,
+        Action::Practice,
+        Action::Test,
+        Action::Refresh,
+        Action::TogglePage,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::Toggle,
+        Action::ToggleAll,
+        Action::Confirm,
+        Action::Cancel,
+    ];
+
+    /// Name used in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Select => "select",
+            Action::Back => "back",
+            Action::Help => "help",
+            Action::Show => "show",
+            Action::Correct => "correct",
+            Action::Wrong => "wrong",
+            Action::Mark => "mark",
+            Action::Next => "next",
+            Action::Insert => "insert",
+            Action::Submit => "submit",
+            Action::LeaveInsert => "leave_insert",
+            Action::DeleteChar => "delete_char",
+            Action::Practice => "practice",
+            Action::Test => "test",
+            Action::Refresh => "refresh",
+            Action::TogglePage => "toggle_page",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::Toggle => "toggle",
+            Action::ToggleAll => "toggle_all",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+        }
+    }
+}
+
+/// Name of `context` in the config file.
+fn context_name(context: Context) -> &'static str {
+    match context {
+        Context::Screen(Screen::Menu) => "menu",
+        Context::Screen(Screen::Practice) => "practice",
+        Context::Screen(Screen::Test) => "test",
+        Context::Screen(Screen::TutorialPrompt) => "tutorial_prompt",
+        Context::Screen(Screen::Tutorial) => "tutorial",
+        Context::Screen(Screen::Stats) => "stats",
+        Context::Screen(Screen::Groups) => "groups",
+        Context::Screen(Screen::Settings) => "settings",
+        Context::Checklist => "checklist",
+    }
+}
+
+/// Whether `a` and `b` are never looked up at the same time in `context`,
+/// so they may share keys. Typing keys in the test only count in insert
+/// mode, the tutorial's confirm and cancel only while it asks whether to
+/// leave, and each tutorial step waits for its own action.
+fn may_share(context: Context, a: Action, b: Action) -> bool {
+    use Action::*;
+    let pair = |x: &[Action], y: &[Action]| {
+        (x.contains(&a) && y.contains(&b)) || (x.contains(&b) && y.contains(&a))
+    };
+    let steps = [Up, Down, Show, Correct, Wrong, Mark, Next];
+    match context {
+        Context::Screen(Screen::Test) => pair(&[LeaveInsert, DeleteChar], &[Insert, Mark, Back]),
+        Context::Screen(Screen::Tutorial) => {
+            let asking = [Confirm, Cancel];
+            (asking.contains(&a) != asking.contains(&b) && a != Help && b != Help)
+                || (steps.contains(&a) && steps.contains(&b))
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub action: Action,
+    pub keys: Vec<KeyCode>,
+    /// What the action does on this screen, shown in the help overlay
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    screens: HashMap<Context, Vec<Binding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use Action::*;
+        use KeyCode::{Backspace, Char, Down as DownKey, Enter, Esc, Left as LeftKey};
+        use KeyCode::{PageDown, PageUp, Right as RightKey, Up as UpKey};
+
+        let navigation = |select: &'static str| {
+            vec![
+                bind(Up, [UpKey, Char('k')], "Move up"),
+                bind(Down, [DownKey, Char('j')], "Move down"),
+                bind(Select, [Enter], select),
+            ]
+        };
+
+        let mut menu = navigation("Open the highlighted item");
+        menu.push(bind(Back, [Esc, Char('q')], "Quit"));
+
+        let practice = vec![
+            bind(Show, [Char('s')], "Show the definition"),
+            bind(Correct, [Char('y')], "I knew it"),
+            bind(Wrong, [Char('n')], "I didn't know it"),
+            bind(Mark, [Char('m')], "Mark or unmark the word"),
+            bind(Next, [Enter], "Next word, once graded"),
+            bind(ScrollUp, [PageUp], "Scroll the definition up"),
+            bind(ScrollDown, [PageDown], "Scroll the definition down"),
+            bind(Back, [Char('q'), Esc], "Back to the menu"),
+        ];
+
+        let test = vec![
+            bind(Insert, [Char('i')], "Start typing"),
+            bind(Submit, [Enter], "Check the answer, then next word"),
+            bind(Mark, [Char('m')], "Mark or unmark the word"),
+            bind(ScrollUp, [PageUp], "Scroll the definition up"),
+            bind(ScrollDown, [PageDown], "Scroll the definition down"),
+            bind(Back, [Char('q'), Esc], "Back to the menu"),
+            bind(LeaveInsert, [Esc], "Stop typing"),
+            bind(
+                DeleteChar,
+                [Backspace],
+                "Delete the last letter while typing",
+            ),
+        ];
+
+        let stats = vec![
+            bind(TogglePage, [Char('h')], "Switch between charts and heatmap"),
+            bind(Refresh, [Char('r')], "Refresh"),
+            bind(Back, [Char('q'), Esc], "Back to the menu"),
+        ];
+
+        let groups = vec![
+            bind(Up, [UpKey, Char('k')], "Move up"),
+            bind(Down, [DownKey, Char('j')], "Move down"),
+            bind(Practice, [Enter, Char('p')], "Practise the group"),
+            bind(Test, [Char('t')], "Test yourself on the group"),
+            bind(Back, [Char('q'), Esc], "Back to the menu"),
+        ];
+
+        let settings = vec![
+            bind(Up, [UpKey, Char('k')], "Move up"),
+            bind(Down, [DownKey, Char('j')], "Move down"),
+            bind(Right, [RightKey, Char('l'), Enter, Char(' ')], "Next value"),
+            bind(Left, [LeftKey, Char('h')], "Previous value"),
+            bind(Back, [Char('q'), Esc], "Back to the menu"),
+        ];
+
+        let tutorial = vec![
+            bind(Back, [Char('q'), Esc], "Leave the tutorial"),
+            bind(Up, [UpKey, Char('k')], "Move up in the menu"),
+            bind(Down, [DownKey, Char('j')], "Move down in the menu"),
+            bind(Show, [Char('s')], "Show the definition"),
+            bind(Correct, [Char('y')], "I knew it"),
+            bind(Wrong, [Char('n')], "I didn't know it"),
+            bind(Mark, [Char('m')], "Mark or unmark the word"),
+            bind(Next, [Enter], "Select, or next word"),
+            bind(Confirm, [Char('y'), Char('Y')], "Leave, when asked"),
+            bind(Cancel, [Char('n'), Char('N'), Esc], "Stay, when asked"),
+        ];
+
+        // Toggle and Confirm come first so presets can put Space and Enter on Right
+        let checklist = vec![
+            bind(Up, [UpKey, Char('k')], "Move up"),
+            bind(Down, [DownKey, Char('j')], "Move down"),
+            bind(Toggle, [Char(' ')], "Tick or untick the word"),
+            bind(ToggleAll, [Char('a')], "Tick or untick every word"),
+            bind(Confirm, [Enter], "Add the ticked words"),
+            bind(Right, [Char('+'), RightKey], "Next group"),
+            bind(Left, [Char('-'), LeftKey], "Previous group"),
+            bind(Back, [Char('q'), Esc], "Add nothing"),
+        ];
+
+        let mut screens = HashMap::from([
+            (Screen::Menu.into(), menu),
+            (Screen::Practice.into(), practice),
+            (Screen::Test.into(), test),
+            (Screen::Stats.into(), stats),
+            (Screen::Groups.into(), groups),
+            (Screen::Settings.into(), settings),
+            (Screen::TutorialPrompt.into(), navigation("Choose")),
+            (Screen::Tutorial.into(), tutorial),
+            (Context::Checklist, checklist),
+        ]);
+        for bindings in screens.values_mut() {
+            bindings.push(bind(Help, [Char('?')], "Show this help"));
+        }
+
+        Self { screens }
+    }
+}
+
+impl Keymap {
+    /// One of the `PRESETS`.
+    pub fn preset(name: &str) -> Option<Self> {
+        use Action::*;
+        use KeyCode::{Char, Down as DownKey, Enter, Esc, F, Left as LeftKey, Right as RightKey};
+        use KeyCode::{Tab, Up as UpKey};
+
+        let mut keymap = Self::default();
+        match name {
+            "default" => {}
+            "vim" => {
+                keymap.set(None, Up, &[Char('k')]);
+                keymap.set(None, Down, &[Char('j')]);
+                keymap.set(None, Left, &[Char('h')]);
+                keymap.set(None, Right, &[Char('l'), Enter, Char(' ')]);
+                keymap.set(Some(Screen::Menu.into()), Select, &[Enter, Char('l')]);
+                keymap.set(
+                    Some(Screen::TutorialPrompt.into()),
+                    Select,
+                    &[Enter, Char('l')],
+                );
+                keymap.set(Some(Screen::Test.into()), Insert, &[Char('i'), Char('a')]);
+                keymap.set(Some(Screen::Practice.into()), Show, &[Char('s'), Char(' ')]);
+                keymap.set(Some(Context::Checklist), Right, &[Char('l'), Char('+')]);
+                keymap.set(Some(Context::Checklist), Left, &[Char('h'), Char('-')]);
+            }
+            "arrows-only" => {
+                // Nothing depends on where letters are on the keyboard
+                keymap.set(None, Up, &[UpKey]);
+                keymap.set(None, Down, &[DownKey]);
+                keymap.set(None, Left, &[LeftKey]);
+                keymap.set(None, Right, &[RightKey, Enter]);
+                keymap.set(None, Back, &[Esc]);
+                keymap.set(None, Help, &[F(1), Char('?')]);
+                keymap.set(None, Mark, &[F(2)]);
+                keymap.set(None, Show, &[DownKey, Char(' ')]);
+                keymap.set(None, Correct, &[RightKey]);
+                keymap.set(None, Wrong, &[LeftKey]);
+                keymap.set(Some(Screen::Test.into()), Insert, &[Tab]);
+                keymap.set(Some(Screen::Groups.into()), Practice, &[Enter]);
+                keymap.set(Some(Screen::Groups.into()), Test, &[Tab]);
+                keymap.set(Some(Screen::Stats.into()), TogglePage, &[Tab]);
+                keymap.set(Some(Screen::Stats.into()), Refresh, &[F(5)]);
+                keymap.set(Some(Screen::Tutorial.into()), Confirm, &[Enter]);
+                keymap.set(Some(Screen::Tutorial.into()), Cancel, &[Esc]);
+                keymap.set(Some(Context::Checklist), Right, &[RightKey]);
+                keymap.set(Some(Context::Checklist), ToggleAll, &[Tab]);
+            }
+            _ => return None,
+        }
+        Some(keymap)
+    }
+
+    /// The `preset` with the `[keys]` section of `config` applied on top.
+    ///
+    /// Entries are `action = keys` for every screen with that action, or
+    /// `screen.action = keys` for one screen, e.g. `practice.show = Space, s`.
+    pub fn build(preset: &str, config: &Config) -> Result<Self> {
+        let mut keymap = Self::preset(preset).unwrap_or_default();
+        for entry in config.section("keys") {
+            let (screen, action) = match entry.key.split_once('.') {
+                Some((screen, action)) => {
+                    let Some(screen) = CONTEXTS.into_iter().find(|c| context_name(*c) == screen)
+                    else {
+                        return Err(config.error(entry, format!("unknown screen `{screen}`")));
+                    };
+                    (Some(screen), action)
+                }
+                None => (None, entry.key.as_str()),
+            };
+            let Some(action) = Action::ALL.into_iter().find(|a| a.name() == action) else {
+                return Err(config.error(entry, format!("unknown action `{action}`")));
+            };
+
+            let keys = entry
+                .value
+                .split(',')
+                .map(|k| parse_key(k.trim()))
+                .collect::<Option<Vec<_>>>();
+            let Some(keys) = keys.filter(|k| !k.is_empty()) else {
+                return Err(config.error(entry, format!("bad key list `{}`", entry.value)));
+            };
+
+            if !keymap.set(screen, action, &keys) {
+                let place = screen.map_or("any screen", context_name);
+                return Err(config.error(
+                    entry,
+                    format!("`{}` is not an action on {place}", action.name()),
+                ));
+            }
+
+            // Otherwise the first of the two would win without a word
+            for context in CONTEXTS {
+                if screen.is_some_and(|screen| screen != context) {
+                    continue;
+                }
+                if let Some((key, other)) = keymap.conflict(context, action) {
+                    return Err(config.error(
+                        entry,
+                        format!(
+                            "`{}` is already `{}` on {}",
+                            key_name(key),
+                            other.name(),
+                            context_name(context)
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// A key of `action` in `context` that another action there also uses,
+    /// with that action.
+    fn conflict(&self, context: Context, action: Action) -> Option<(KeyCode, Action)> {
+        let bindings = self.bindings(context);
+        let keys = bindings
+            .iter()
+            .filter(|b| b.action == action)
+            .flat_map(|b| &b.keys);
+        for key in keys {
+            if let Some(other) = bindings.iter().find(|b| {
+                b.action != action && b.keys.contains(key) && !may_share(context, action, b.action)
+            }) {
+                return Some((*key, other.action));
+            }
+        }
+        None
+    }
+
+    /// Bind `action` to `keys` on `screen`, or on every screen that has the
+    /// action. Returns `false` if no screen has it.
+    fn set(&mut self, screen: Option<Context>, action: Action, keys: &[KeyCode]) -> bool {
+        let mut found = false;
+        for (s, bindings) in &mut self.screens {
+            if screen.is_some_and(|screen| screen != *s) {
+                continue;
+            }
+            for binding in bindings.iter_mut().filter(|b| b.action == action) {
+                binding.keys = keys.to_vec();
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// The first key for `action` on `screen`, as shown on buttons.
+    pub fn label(&self, screen: impl Into<Context>, action: Action) -> String {
+        match self
+            .bindings(screen)
+            .iter()
+            .find(|b| b.action == action)
+            .and_then(|b| b.keys.first())
+        {
+            Some(KeyCode::Enter) => "⏎".into(),
+            Some(key) => key_name(*key),
+            None => String::new(),
+        }
+    }
+
+    /// A press of the first key bound to `action`, for clicks that stand in
+    /// for keys.
+    pub fn key(&self, screen: impl Into<Context>, action: Action) -> Option<KeyEvent> {
+        self.bindings(screen)
+            .iter()
+            .find(|b| b.action == action)
+            .and_then(|b| b.keys.first())
+            .map(|code| KeyEvent::new(*code, KeyModifiers::empty()))
+    }
+
+    pub fn bindings(&self, screen: impl Into<Context>) -> &[Binding] {
+        self.screens.get(&screen.into()).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first action on `screen` bound to `key`.
+    pub fn action(&self, screen: impl Into<Context>, key: &KeyEvent) -> Option<Action> {
+        self.bindings(screen)
+            .iter()
+            .find(|b| b.keys.contains(&key.code))
+            .map(|b| b.action)
+    }
+
+    /// Whether `key` triggers `action` on `screen`, for keys that mean
+    /// different things depending on the state of the screen.
+    pub fn matches(&self, screen: impl Into<Context>, action: Action, key: &KeyEvent) -> bool {
+        self.bindings(screen)
+            .iter()
+            .any(|b| b.action == action && b.keys.contains(&key.code))
+    }
+}
+
+fn bind<const N: usize>(action: Action, keys: [KeyCode; N], description: &'static str) -> Binding {
+    Binding {
+        action,
+        keys: keys.to_vec(),
+        description,
+    }
+}
+
+/// A key written as in the help overlay; names are case-insensitive.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "backspace" => KeyCode::Backspace,
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        f => KeyCode::F(f.strip_prefix('f')?.parse().ok()?),
+    };
+    Some(code)
+}
+
+/// How a key is written in the help overlay.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".into(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        KeyCode::PageUp => "PageUp".into(),
+        KeyCode::PageDown => "PageDown".into(),
+        KeyCode::Backspace => "Backspace".into(),
+        KeyCode::Enter => "Enter".into(),
+        KeyCode::Esc => "Esc".into(),
+        KeyCode::Tab => "Tab".into(),
+        KeyCode::Up => "Up".into(),
+        KeyCode::Down => "Down".into(),
+        KeyCode::Left => "Left".into(),
+        KeyCode::Right => "Right".into(),
+        KeyCode::Home => "Home".into(),
+        KeyCode::End => "End".into(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Every key bound to `binding`, e.g. "Up/k".
+pub fn keys_label(binding: &Binding) -> String {
+    binding
+        .keys
+        .iter()
+        .map(|k| key_name(*k))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+
+    #[test]
+    fn test_every_screen_has_help() {
+        let keymap = Keymap::default();
+        for screen in [
+            Screen::Menu,
+            Screen::Practice,
+            Screen::Test,
+            Screen::TutorialPrompt,
+            Screen::Tutorial,
+            Screen::Stats,
+            Screen::Groups,
+            Screen::Settings,
+        ]
+        .map(Context::from)
+        .into_iter()
+        .chain([Context::Checklist])
+        {
+            assert_eq!(
+                keymap.action(screen, &key(KeyCode::Char('?'))),
+                Some(Action::Help),
+                "{screen:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_presets() {
+        let vim = Keymap::preset("vim").unwrap();
+        assert_eq!(vim.action(Screen::Menu, &key(KeyCode::Down)), None);
+        assert_eq!(
+            vim.action(Screen::Menu, &key(KeyCode::Char('l'))),
+            Some(Action::Select)
+        );
+
+        let arrows = Keymap::preset("arrows-only").unwrap();
+        for screen in CONTEXTS {
+            for binding in arrows.bindings(screen) {
+                assert!(
+                    !binding
+                        .keys
+                        .iter()
+                        .any(|k| matches!(k, KeyCode::Char(c) if c.is_alphabetic())),
+                    "{screen:?} {binding:?}"
+                );
+            }
+        }
+        assert!(Keymap::preset("emacs").is_none());
+
+        // Moving Right onto Enter and Space leaves the checklist alone
+        for (keymap, right) in [(vim, KeyCode::Char('l')), (arrows, KeyCode::Right)] {
+            let action = |code| keymap.action(Context::Checklist, &key(code));
+            assert_eq!(action(KeyCode::Enter), Some(Action::Confirm));
+            assert_eq!(action(KeyCode::Char(' ')), Some(Action::Toggle));
+            assert_eq!(action(right), Some(Action::Right));
+        }
+    }
+
+    #[test]
+    fn test_config_overrides_preset() {
+        let config = Config::parse(
+            "test.conf",
+            "[keys]
+mark = b
+practice.show = Space, F3
+",
+        )
+        .unwrap();
+
+        let keymap = Keymap::build("default", &config).unwrap();
+
+        assert_eq!(
+            keymap.action(Screen::Test, &key(KeyCode::Char('b'))),
+            Some(Action::Mark)
+        );
+        assert_eq!(
+            keymap.action(Screen::Practice, &key(KeyCode::Char('m'))),
+            None
+        );
+        assert_eq!(
+            keymap.action(Screen::Practice, &key(KeyCode::F(3))),
+            Some(Action::Show)
+        );
+        // The tutorial follows the same bindings
+        assert!(keymap.matches(Screen::Tutorial, Action::Mark, &key(KeyCode::Char('b'))));
+        assert!(!keymap.matches(Screen::Tutorial, Action::Mark, &key(KeyCode::Char('m'))));
+    }
+
+    #[test]
+    fn test_config_errors() {
+        for (line, message) in [
+            ("fly = x", "unknown action `fly`"),
+            ("lobby.up = x", "unknown screen `lobby`"),
+            ("stats.show = x", "`show` is not an action on stats"),
+            ("up = Hyper", "bad key list `Hyper`"),
+            ("practice.show = m", "`m` is already `mark` on practice"),
+            ("back = Enter", "`Enter` is already `select` on menu"),
+            (
+                "checklist.toggle = a",
+                "`a` is already `toggle_all` on checklist",
+            ),
+        ] {
+            let config = Config::parse("test.conf", &format!("[keys]\n{line}\n")).unwrap();
+            let err = Keymap::build("default", &config).unwrap_err();
+            assert_eq!(err.to_string(), format!("test.conf:2: {message}"));
+        }
+    }
+
+    #[test]
+    fn test_keys_shared_only_where_never_read_together() {
+        for preset in PRESETS {
+            let keymap = Keymap::preset(preset).unwrap();
+            for context in CONTEXTS {
+                for action in Action::ALL {
+                    assert_eq!(
+                        keymap.conflict(context, action),
+                        None,
+                        "{preset} {context:?}"
+                    );
+                }
+            }
+        }
+
+        let config = Config::parse(
+            "test.conf",
+            "[keys]
+test.leave_insert = q, Esc
+tutorial.confirm = Enter
+tutorial.show = Down
+",
+        )
+        .unwrap();
+        assert!(Keymap::build("default", &config).is_ok());
+    }
+
+    #[test]
+    fn test_first_binding_wins() {
+        let keymap = Keymap::default();
+
+        assert_eq!(
+            keymap.action(Screen::Test, &key(KeyCode::Esc)),
+            Some(Action::Back)
+        );
+        assert!(keymap.matches(Screen::Test, Action::LeaveInsert, &key(KeyCode::Esc)));
+        assert_eq!(keys_label(&keymap.bindings(Screen::Menu)[0]), "Up/k");
+    }
+}
@@ -0,0 +1,372 @@
+// Layout
+// Sizes the Practice and Test screens to the terminal: stats and buttons
+// collapse on short terminals, and wide ones keep the content centred
+
+use crate::core::session::Scroll;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+};
+
+/// Narrowest terminal the word screens are drawn in.
+pub const MIN_WIDTH: u16 = 40;
+
+/// Content wider than this is centred instead of stretched.
+pub const MAX_WIDTH: u16 = 100;
+
+/// Where each panel of a word screen goes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordLayout {
+    pub header: Rect,
+    pub word: Rect,
+    pub definition: Rect,
+    /// Zero height on screens without an answer field
+    pub input: Rect,
+    /// Left out when there is no room
+    pub stats: Option<Rect>,
+    pub actions: Rect,
+    /// Actions are a single line of keys rather than buttons
+    pub compact: bool,
+}
+
+/// Heights of the word box, stats, actions and smallest definition panel,
+/// from roomiest to tightest.
+const SIZES: [(u16, u16, u16, u16); 3] = [(5, 4, 5, 5), (5, 0, 5, 3), (3, 0, 1, 3)];
+
+const HEADER: u16 = 3;
+const INPUT: u16 = 3;
+
+impl WordLayout {
+    /// The roomiest layout that fits in `area`, or `None` if even the
+    /// compact one does not.
+    pub fn new(area: Rect, input: bool) -> Option<Self> {
+        if area.width < MIN_WIDTH {
+            return None;
+        }
+        let mut area = area.inner(&Margin::new(1, 1));
+        if area.width > MAX_WIDTH {
+            area.x += (area.width - MAX_WIDTH) / 2;
+            area.width = MAX_WIDTH;
+        }
+
+        let input = if input { INPUT } else { 0 };
+        let (word, stats, actions, definition) =
+            SIZES
+                .into_iter()
+                .find(|(word, stats, actions, definition)| {
+                    HEADER + word + definition + input + stats + actions <= area.height
+                })?;
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(HEADER),
+                Constraint::Length(word),
+                Constraint::Min(definition),
+                Constraint::Length(input),
+                Constraint::Length(stats),
+                Constraint::Length(actions),
+            ])
+            .split(area);
+
+        Some(Self {
+            header: chunks[0],
+            word: chunks[1],
+            definition: chunks[2],
+            input: chunks[3],
+            stats: (stats > 0).then_some(chunks[4]),
+            actions: chunks[5],
+            compact: actions == 1,
+        })
+    }
+
+    /// Smallest terminal the compact layout fits in.
+    pub fn min_size(input: bool) -> (u16, u16) {
+        let (word, stats, actions, definition) = SIZES[SIZES.len() - 1];
+        let input = if input { INPUT } else { 0 };
+        (
+            MIN_WIDTH,
+            HEADER + word + definition + input + stats + actions + 2,
+        )
+    }
+}
+
+/// Shown instead of a word screen that does not fit.
+pub fn render_too_small(frame: &mut Frame, input: bool, theme: &Theme) {
+    let area = frame.size();
+    let (width, height) = WordLayout::min_size(input);
+    let text = vec![
+        Line::styled("Terminal too small", theme.warning),
+        Line::from(format!(
+            "{}x{}, needs {width}x{height}",
+            area.width, area.height
+        )),
+    ];
+    let top = area.height.saturating_sub(text.len() as u16) / 2;
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        Rect {
+            y: area.y + top,
+            height: area.height - top,
+            ..area
+        },
+    );
+}
+
+/// The definition panel, wrapped and scrolled. Records the page size and how
+/// far it can scroll in `scroll`.
+pub fn render_definition(frame: &mut Frame, area: Rect, text: &str, scroll: &Scroll) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+
+    let definition = Paragraph::new(text.to_string())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    // Counted by ratatui so the rows match what is drawn
+    let lines = definition.line_count(inner.width).min(u16::MAX as usize) as u16;
+    let max = lines.saturating_sub(inner.height);
+    scroll.page.set(inner.height);
+    scroll.max.set(max);
+    let offset = scroll.offset.min(max);
+
+    let title = if max > 0 {
+        let last = (offset + inner.height).min(lines);
+        format!("Definition ({}-{last} of {lines})", offset + 1)
+    } else {
+        "Definition".into()
+    };
+
+    frame.render_widget(
+        definition.scroll((offset, 0)).block(block.title(title)),
+        area,
+    );
+}
+
+/// The `(label, key)` buttons in the actions panel, or one line of
+/// `[key] Label` pairs in the compact layout.
+pub fn render_buttons(
+    frame: &mut Frame,
+    layout: &WordLayout,
+    buttons: &[(&str, String)],
+    theme: &Theme,
+) {
+    if layout.compact {
+        let mut spans = Vec::new();
+        for (label, key) in buttons {
+            spans.push(Span::styled(format!("[{key}]"), theme.key));
+            spans.push(Span::raw(format!(" {label}  ")));
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).alignment(Alignment::Center),
+            layout.actions,
+        );
+        return;
+    }
+
+    let block = Block::default()
+        .title("Actions")
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1));
+    frame.render_widget(block, layout.actions);
+
+    for ((label, key), area) in buttons.iter().zip(button_areas(layout, buttons.len())) {
+        let content = Line::from(vec![
+            Span::styled(*label, Style::default().bold()),
+            Span::raw("\n"),
+            Span::styled(format!("[{key}]"), theme.key),
+        ]);
+        let button = Paragraph::new(content)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(button, area);
+    }
+}
+
+/// Index of the button drawn by `render_buttons` at column `x`, row `y`.
+pub fn button_at(layout: &WordLayout, buttons: &[(&str, String)], x: u16, y: u16) -> Option<usize> {
+    let position = Position { x, y };
+    if !layout.actions.contains(position) {
+        return None;
+    }
+    if !layout.compact {
+        return button_areas(layout, buttons.len())
+            .iter()
+            .position(|area| area.contains(position));
+    }
+
+    // Each pair is `[key] Label` and two spaces, the line centred
+    let widths: Vec<u16> = buttons
+        .iter()
+        .map(|(label, key)| (key.chars().count() + label.chars().count() + 5) as u16)
+        .collect();
+    let total: u16 = widths.iter().sum();
+    let mut left = layout.actions.x + layout.actions.width.saturating_sub(total) / 2;
+    for (i, width) in widths.into_iter().enumerate() {
+        if (left..left + width).contains(&x) {
+            return Some(i);
+        }
+        left += width;
+    }
+    None
+}
+
+/// Index of the row at column `x`, row `y` of a list or table whose rows are
+/// drawn in `rows`. Like ratatui, the rows are scrolled just far enough to
+/// show `selected`.
+pub fn row_at(rows: Rect, len: usize, selected: usize, x: u16, y: u16) -> Option<usize> {
+    if !rows.contains(Position { x, y }) {
+        return None;
+    }
+    let offset = (selected + 1).saturating_sub(rows.height as usize);
+    let index = offset + (y - rows.y) as usize;
+    (index < len).then_some(index)
+}
+
+/// Equal slots for `count` buttons inside the actions panel.
+fn button_areas(layout: &WordLayout, count: usize) -> Vec<Rect> {
+    let inner = layout.actions.inner(&Margin::new(2, 1));
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, count as u32); count])
+        .split(inner)
+        .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(width: u16, height: u16) -> Rect {
+        Rect::new(0, 0, width, height)
+    }
+
+    #[test]
+    fn test_row_at_follows_scrolling() {
+        let rows = Rect::new(1, 1, 10, 3);
+
+        assert_eq!(row_at(rows, 5, 0, 2, 1), Some(0));
+        assert_eq!(row_at(rows, 2, 0, 2, 3), None);
+        assert_eq!(row_at(rows, 5, 0, 0, 1), None);
+        // With the fifth row selected the list shows rows 2 to 4
+        assert_eq!(row_at(rows, 5, 4, 2, 1), Some(2));
+        assert_eq!(row_at(rows, 5, 4, 2, 3), Some(4));
+    }
+
+    #[test]
+    fn test_layout_collapses_when_short() {
+        let full = WordLayout::new(area(80, 30), true).unwrap();
+        assert!(full.stats.is_some());
+        assert!(!full.compact);
+        assert_eq!(full.input.height, 3);
+
+        let medium = WordLayout::new(area(80, 22), false).unwrap();
+        assert!(medium.stats.is_none());
+        assert!(!medium.compact);
+
+        let compact = WordLayout::new(area(80, 12), false).unwrap();
+        assert!(compact.compact);
+        assert_eq!(compact.actions.height, 1);
+        assert_eq!(compact.input.height, 0);
+
+        assert_eq!(WordLayout::min_size(false), (MIN_WIDTH, 12));
+        assert!(WordLayout::new(area(80, 11), false).is_none());
+        assert!(WordLayout::new(area(80, 14), true).is_none());
+        assert!(WordLayout::new(area(39, 30), false).is_none());
+    }
+
+    #[test]
+    fn test_wide_terminals_are_centred() {
+        let layout = WordLayout::new(area(300, 40), false).unwrap();
+
+        assert_eq!(layout.header.width, MAX_WIDTH);
+        assert_eq!(layout.header.x, 100);
+        // Spare height goes to the definition
+        assert_eq!(layout.definition.height, 40 - 2 - 3 - 5 - 4 - 5);
+    }
+
+    #[test]
+    fn test_button_at_in_compact_line() {
+        let layout = WordLayout::new(area(40, 12), false).unwrap();
+        let buttons = [("Show", "s".to_string()), ("Next", "⏎".to_string())];
+        // "[s] Show  [⏎] Next  " is 20 wide, centred in 38 columns from x 1
+        let row = layout.actions.y;
+
+        assert_eq!(button_at(&layout, &buttons, 10, row), Some(0));
+        assert_eq!(button_at(&layout, &buttons, 20, row), Some(1));
+        assert_eq!(button_at(&layout, &buttons, 9, row), None);
+        assert_eq!(button_at(&layout, &buttons, 10, row - 1), None);
+    }
+
+    #[test]
+    fn test_definition_reaches_last_line_with_runs_of_spaces() {
+        // Runs of spaces and tabs inside a line take up room when wrapped
+        let text = "one  \t   two      three\n".repeat(6) + "last";
+        let mut scroll = Scroll::default();
+        let backend = ratatui::backend::TestBackend::new(20, 7);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+
+        terminal
+            .draw(|f| render_definition(f, f.size(), &text, &scroll))
+            .unwrap();
+        let rows = Paragraph::new(text.as_str())
+            .wrap(Wrap { trim: true })
+            .line_count(16) as u16;
+        // Two rows each, not the one the words alone would need
+        assert_eq!(rows, 13);
+        assert_eq!(scroll.max.get(), rows - 5);
+
+        scroll.down(u16::MAX);
+        terminal
+            .draw(|f| render_definition(f, f.size(), &text, &scroll))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("last"));
+    }
+
+    #[test]
+    fn test_definition_scrolls() {
+        let text = "line\n".repeat(20);
+        let mut scroll = Scroll::default();
+        let backend = ratatui::backend::TestBackend::new(40, 7);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+
+        terminal
+            .draw(|f| render_definition(f, f.size(), &text, &scroll))
+            .unwrap();
+        assert_eq!((scroll.page.get(), scroll.max.get()), (5, 15));
+
+        scroll.page_down();
+        scroll.page_down();
+        scroll.page_down();
+        scroll.page_down();
+        assert_eq!(scroll.offset, 15);
+        scroll.page_up();
+        assert_eq!(scroll.offset, 10);
+
+        terminal
+            .draw(|f| render_definition(f, f.size(), &text, &scroll))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Definition (11-15 of 20)"));
+    }
+}
@@ -1,4 +1,9 @@
 pub mod app;
+pub mod checklist;
+pub mod keymap;
+pub mod layout;
+pub mod mouse;
 pub mod run;
 pub mod screens;
 pub mod terminal;
+pub mod theme;
@@ -0,0 +1,258 @@
+// Mouse
+// Clicks and the scroll wheel, turned into the key presses the screens
+// already handle
+
+use crate::ui::app::{App, Screen};
+use crate::ui::keymap::Action;
+use crate::ui::layout::{self, WordLayout};
+use crate::ui::screens::{groups, menu, practice, settings, test, tutorial, tutorial_prompt};
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+/// Lines the definition moves per notch of the scroll wheel.
+const WHEEL_LINES: u16 = 1;
+
+/// What `mouse` means on the current screen, drawn in `area`: a key press to
+/// hand to the screen, or `None` if it was dealt with or missed.
+pub fn handle_event(app: &mut App, mouse: MouseEvent, area: Rect) -> Option<KeyEvent> {
+    if app.show_help {
+        // A click closes the overlay, like any key
+        if let MouseEventKind::Down(_) = mouse.kind {
+            app.show_help = false;
+        }
+        return None;
+    }
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => click(app, mouse.column, mouse.row, area),
+        MouseEventKind::ScrollDown => wheel(app, true),
+        MouseEventKind::ScrollUp => wheel(app, false),
+        _ => None,
+    }
+}
+
+fn click(app: &mut App, x: u16, y: u16, area: Rect) -> Option<KeyEvent> {
+    let screen = app.current_screen;
+    let action = match screen {
+        Screen::Menu => {
+            app.selected = menu::item_at(app, area, x, y)?;
+            Action::Select
+        }
+        Screen::TutorialPrompt => {
+            app.selected = tutorial_prompt::item_at(app, area, x, y)?;
+            Action::Select
+        }
+        Screen::Groups => {
+            app.group_selected = groups::item_at(app, area, x, y)?;
+            Action::Practice
+        }
+        Screen::Settings => {
+            app.settings_selected = settings::item_at(app, area, x, y)?;
+            Action::Right
+        }
+        Screen::Tutorial => tutorial::button_at(app, area, x, y)?,
+        Screen::Practice => button_at(app, &practice::BUTTONS, false, area, x, y)?,
+        Screen::Test => {
+            let action = button_at(app, &test::BUTTONS, true, area, x, y)?;
+            // While typing, keys other than Enter would land in the answer
+            if action != Action::Submit
+                && let Some(session) = &mut app.session
+            {
+                session.insert_mode = false;
+            }
+            action
+        }
+        _ => return None,
+    };
+    app.keymap.key(screen, action)
+}
+
+/// The action of the word screen button at `x`, `y`.
+fn button_at(
+    app: &App,
+    buttons: &[(&'static str, Action)],
+    input: bool,
+    area: Rect,
+    x: u16,
+    y: u16,
+) -> Option<Action> {
+    let layout = WordLayout::new(area, input)?;
+    let labels: Vec<(&str, String)> = buttons
+        .iter()
+        .map(|(label, action)| (*label, app.keymap.label(app.current_screen, *action)))
+        .collect();
+    layout::button_at(&layout, &labels, x, y).map(|i| buttons[i].1)
+}
+
+/// Scroll the definition on word screens, move the selection on lists.
+fn wheel(app: &mut App, down: bool) -> Option<KeyEvent> {
+    match app.current_screen {
+        Screen::Practice | Screen::Test => {
+            let session = app.session.as_mut()?;
+            if down {
+                session.scroll.down(WHEEL_LINES);
+            } else {
+                session.scroll.up(WHEEL_LINES);
+            }
+            None
+        }
+        Screen::Menu | Screen::TutorialPrompt | Screen::Groups | Screen::Settings => {
+            let action = if down { Action::Down } else { Action::Up };
+            app.keymap.key(app.current_screen, action)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::session::{Session, Type};
+    use crate::db::{init_db, models::Word};
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    fn click_at(app: &mut App, column: u16, row: u16) -> Option<KeyEvent> {
+        let event = mouse(MouseEventKind::Down(MouseButton::Left), column, row);
+        handle_event(app, event, Rect::new(0, 0, 80, 30))
+    }
+
+    fn practice_app() -> App {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        let word = Word {
+            definition: "line\n".repeat(30),
+            ..Word::default()
+        };
+        app.session = Some(Session::new(vec![word], 0, Type::Group));
+        app.current_screen = Screen::Practice;
+        app
+    }
+
+    #[test]
+    fn test_click_menu_item() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.goal = None;
+
+        // Border on row 0, items from row 1
+        let key = click_at(&mut app, 5, 3);
+        assert_eq!(app.selected, 2);
+        assert_eq!(key.map(|k| k.code), Some(KeyCode::Enter));
+
+        assert_eq!(click_at(&mut app, 5, 25), None);
+        assert_eq!(app.selected, 2);
+    }
+
+    #[test]
+    fn test_click_list_rows() {
+        let conn = init_db(":memory:").unwrap();
+        for (word, group) in [("abound", 1), ("belie", 2)] {
+            crate::db::queries::insert_word(&conn, word, "", group, "").unwrap();
+        }
+        let mut app = App::new(conn);
+
+        // Full-screen list: border on row 0, options from row 1
+        app.current_screen = Screen::TutorialPrompt;
+        let key = click_at(&mut app, 5, 2);
+        assert_eq!(app.selected, 1);
+        assert_eq!(key.map(|k| k.code), Some(KeyCode::Enter));
+        assert_eq!(click_at(&mut app, 5, 3), None);
+
+        // Margin, border, then the settings
+        app.current_screen = Screen::Settings;
+        let key = click_at(&mut app, 5, 3);
+        assert_eq!(app.settings_selected, 1);
+        assert_eq!(key.map(|k| k.code), Some(KeyCode::Right));
+
+        // ...and a header row before the groups
+        app.current_screen = Screen::Groups;
+        app.groups = crate::core::stats::group_summaries(&app.conn).unwrap();
+        let key = click_at(&mut app, 5, 4);
+        assert_eq!(app.group_selected, 1);
+        assert_eq!(key.map(|k| k.code), Some(KeyCode::Enter));
+        assert_eq!(click_at(&mut app, 5, 2), None);
+        assert_eq!(app.group_selected, 1);
+    }
+
+    #[test]
+    fn test_click_tutorial_buttons() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.current_screen = Screen::Tutorial;
+        app.tutorial_state = Some(crate::core::tutorial::init_tutorial());
+
+        // The welcome step has no buttons
+        assert_eq!(click_at(&mut app, 5, 26), None);
+
+        app.tutorial_state.as_mut().unwrap().current_step = 5;
+        let show = click_at(&mut app, 5, 26);
+        assert_eq!(show.map(|k| k.code), Some(KeyCode::Char('s')));
+        let next = click_at(&mut app, 75, 26);
+        assert_eq!(next.map(|k| k.code), Some(KeyCode::Enter));
+        assert_eq!(click_at(&mut app, 5, 12), None);
+    }
+
+    #[test]
+    fn test_click_buttons() {
+        let mut app = practice_app();
+        let layout = WordLayout::new(Rect::new(0, 0, 80, 30), false).unwrap();
+        let row = layout.actions.y + 2;
+
+        // Five buttons across the actions panel
+        let show = click_at(&mut app, layout.actions.x + 5, row);
+        assert_eq!(show.map(|k| k.code), Some(KeyCode::Char('s')));
+        let next = click_at(&mut app, layout.actions.right() - 5, row);
+        assert_eq!(next.map(|k| k.code), Some(KeyCode::Enter));
+        assert_eq!(click_at(&mut app, 5, layout.word.y), None);
+    }
+
+    #[test]
+    fn test_click_in_test_mode_leaves_insert_mode() {
+        let mut app = practice_app();
+        app.current_screen = Screen::Test;
+        app.session.as_mut().unwrap().insert_mode = true;
+        let layout = WordLayout::new(Rect::new(0, 0, 80, 30), true).unwrap();
+
+        // Mark, the second of four buttons
+        let key = click_at(
+            &mut app,
+            layout.actions.x + layout.actions.width * 3 / 8,
+            layout.actions.y + 2,
+        );
+
+        assert_eq!(key.map(|k| k.code), Some(KeyCode::Char('m')));
+        assert!(!app.session.as_ref().unwrap().insert_mode);
+    }
+
+    #[test]
+    fn test_wheel_scrolls_definition() {
+        let mut app = practice_app();
+        app.session.as_mut().unwrap().show_definition = true;
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal.draw(|f| practice::render(f, &app)).unwrap();
+
+        let area = Rect::new(0, 0, 80, 30);
+        handle_event(&mut app, mouse(MouseEventKind::ScrollDown, 10, 10), area);
+        handle_event(&mut app, mouse(MouseEventKind::ScrollDown, 10, 10), area);
+        handle_event(&mut app, mouse(MouseEventKind::ScrollUp, 10, 10), area);
+
+        assert_eq!(app.session.as_ref().unwrap().scroll.offset, 1);
+    }
+
+    #[test]
+    fn test_click_closes_help() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.show_help = true;
+
+        assert_eq!(click_at(&mut app, 5, 3), None);
+        assert!(!app.show_help);
+        assert_eq!(app.selected, 0);
+    }
+}
@@ -1,5 +1,12 @@
 use crate::core::tutorial::{is_tutorial_completed, should_auto_advance};
-use crate::ui::screens::{menu, practice, test, tutorial, tutorial_prompt};
+use crate::audio;
+use crate::config::{CONFIG_PATH, Config};
+use crate::core::settings::Settings;
+use crate::ui::keymap::Keymap;
+use crate::ui::theme::Theme;
+use crate::ui::screens::{
+    groups, help, menu, practice, settings, stats, test, tutorial, tutorial_prompt,
+};
 use anyhow::Result;
 use crossterm::event::{self, Event};
 use rusqlite::Connection;
@@ -7,12 +14,18 @@ use std::time::Duration;
 
 use super::{
     app::{App, Screen},
+    mouse,
     terminal::{init_terminal, restore_terminal},
 };
 
 pub fn run() -> Result<()> {
-    let mut terminal = init_terminal()?;
+    // Mistakes in the config file are reported before the screen is taken over
+    let config = Config::load(CONFIG_PATH)?;
     let conn = Connection::open("vocab.db")?;
+    let settings = Settings::load(&conn)?;
+    let keymap = Keymap::build(&settings.keys, &config)?;
+    let theme = Theme::load(&settings.theme, &config)?;
+    let mut terminal = init_terminal()?;
     
     // Check tutorial completion status and set initial screen
     let initial_screen = if is_tutorial_completed(&conn)? {
@@ -23,18 +36,30 @@ pub fn run() -> Result<()> {
     
     let mut app = App::new(conn);
     app.current_screen = initial_screen;
+    app.keymap = keymap;
+    app.theme = theme;
+    app.config = config;
+    audio::set_enabled(app.settings.sounds);
 
     loop {
-        terminal.draw(|f| match app.current_screen {
-            Screen::Menu => menu::render(f, &app),
-            Screen::Practice => practice::render(f, &app),
-            Screen::Test => test::render(f, &app),
-            Screen::TutorialPrompt => tutorial_prompt::render(f, &app),
-            Screen::Tutorial => tutorial::render(f, &app),
+        terminal.draw(|f| {
+            match app.current_screen {
+                Screen::Menu => menu::render(f, &app),
+                Screen::Practice => practice::render(f, &app),
+                Screen::Test => test::render(f, &app),
+                Screen::TutorialPrompt => tutorial_prompt::render(f, &app),
+                Screen::Tutorial => tutorial::render(f, &app),
+                Screen::Stats => stats::render(f, &app),
+                Screen::Groups => groups::render(f, &app),
+                Screen::Settings => settings::render(f, &app),
+            }
+            if app.show_help {
+                help::render(f, &app);
+            }
         })?;
 
         // Check for auto-advance in tutorial step 4
-        if app.current_screen == Screen::Tutorial {
+        if app.current_screen == Screen::Tutorial && app.settings.tutorial_auto_advance {
             if let Some(ref mut tutorial_state) = app.tutorial_state {
                 if should_auto_advance(tutorial_state) {
                     // Auto-advance from step 4 to step 5
@@ -48,13 +73,22 @@ pub fn run() -> Result<()> {
 
         // Poll for events with a timeout to allow auto-advance checking
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let key = match event::read()? {
+                Event::Key(key) if !help::handle_event(&mut app, key) => Some(key),
+                // Clicks stand in for the key of what was clicked
+                Event::Mouse(mouse) => mouse::handle_event(&mut app, mouse, terminal.size()?),
+                _ => None,
+            };
+            if let Some(key) = key {
                 match app.current_screen {
                     Screen::Menu => menu::handle_event(&mut app, key),
                     Screen::Practice => practice::handle_event(&mut app, key),
                     Screen::Test => test::handle_event(&mut app, key),
                     Screen::TutorialPrompt => tutorial_prompt::handle_event(&mut app, key),
                     Screen::Tutorial => tutorial::handle_event(&mut app, key),
+                    Screen::Stats => stats::handle_event(&mut app, key),
+                    Screen::Groups => groups::handle_event(&mut app, key),
+                    Screen::Settings => settings::handle_event(&mut app, key),
                 }
             }
         }
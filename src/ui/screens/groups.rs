@@ -0,0 +1,245 @@
+use crate::audio;
+use crate::core::session;
+use crate::core::stats::{GroupSummary, percent};
+use crate::ui::app::{App, Screen};
+use crate::ui::keymap::Action;
+use crate::ui::layout::row_at;
+use crate::ui::theme::Theme;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+use std::rc::Rc;
+
+/// Width of the mastery bar in cells.
+const BAR_WIDTH: usize = 20;
+
+pub fn handle_event(app: &mut App, key: KeyEvent) {
+    app.error = None;
+    match app.keymap.action(Screen::Groups, &key) {
+        Some(Action::Back) => {
+            app.groups.clear();
+            app.current_screen = Screen::Menu;
+        }
+        Some(Action::Down) if !app.groups.is_empty() => {
+            app.group_selected = (app.group_selected + 1) % app.groups.len();
+            audio::play_menu_sound();
+        }
+        Some(Action::Up) if !app.groups.is_empty() => {
+            app.group_selected = (app.group_selected + app.groups.len() - 1) % app.groups.len();
+            audio::play_menu_sound();
+        }
+        Some(Action::Practice) => start(app, Screen::Practice),
+        Some(Action::Test) => start(app, Screen::Test),
+        _ => {}
+    }
+}
+
+/// Start the highlighted group in `screen`, making it the saved position.
+fn start(app: &mut App, screen: Screen) {
+    let Some(group) = app.groups.get(app.group_selected).map(|g| g.group) else {
+        return;
+    };
+
+    match session::choose_group(&app.conn, group, screen) {
+        Ok(session) if session.words.is_empty() => {
+            app.error = Some(format!("Group {group} has no words"));
+        }
+        Ok(session) => {
+            app.session = Some(session);
+            app.groups.clear();
+            app.current_screen = screen;
+        }
+        Err(e) => app.error = Some(e.to_string()),
+    }
+}
+
+/// Groups table and footer.
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Groups
+            Constraint::Length(3), // Keys or error
+        ])
+        .split(area)
+}
+
+/// Index of the group at column `x`, row `y`.
+pub fn item_at(app: &App, area: Rect, x: u16, y: u16) -> Option<usize> {
+    let table = layout(area)[0].inner(&Margin::new(1, 1));
+    // Below the header row
+    let rows = Rect {
+        y: table.y + 1,
+        height: table.height.saturating_sub(1),
+        ..table
+    };
+    row_at(rows, app.groups.len(), app.group_selected, x, y)
+}
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let layout = layout(frame.size());
+
+    let rows: Vec<Row> = app
+        .groups
+        .iter()
+        .map(|g| group_row(g, &app.theme))
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(9),
+            Constraint::Length(BAR_WIDTH as u16),
+        ],
+    )
+    .header(
+        Row::new(["Group", "Words", "Seen", "Accuracy", "Mastery"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Choose Group "),
+    )
+    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .highlight_symbol("> ");
+
+    let mut state = TableState::default();
+    state.select(Some(app.group_selected));
+    frame.render_stateful_widget(table, layout[0], &mut state);
+
+    render_footer(frame, app, layout[1]);
+}
+
+fn group_row(summary: &GroupSummary, theme: &Theme) -> Row<'static> {
+    Row::new(vec![
+        Cell::from(summary.group.to_string()),
+        Cell::from(summary.totals.words.to_string()),
+        Cell::from(percent(Some(summary.seen_ratio()))),
+        Cell::from(percent(summary.totals.accuracy)),
+        Cell::from(mastery_bar(summary, theme)),
+    ])
+}
+
+/// Mastered, learning and unseen words as a stacked bar.
+fn mastery_bar(summary: &GroupSummary, theme: &Theme) -> Line<'static> {
+    let mastery = &summary.mastery;
+    let total = mastery.total().max(1);
+    let mastered = mastery.mastered * BAR_WIDTH / total;
+    let learning = (mastery.mastered + mastery.learning) * BAR_WIDTH / total - mastered;
+    let unseen = BAR_WIDTH - mastered - learning;
+
+    Line::from(vec![
+        Span::styled("█".repeat(mastered), theme.correct),
+        Span::styled("█".repeat(learning), theme.warning),
+        Span::styled("░".repeat(unseen), theme.muted),
+    ])
+}
+
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL);
+    if let Some(err) = &app.error {
+        frame.render_widget(
+            Paragraph::new(err.clone())
+                .block(block.title("Error"))
+                .style(app.theme.error),
+            area,
+        );
+        return;
+    }
+
+    let key = |action| {
+        Span::styled(
+            format!("[{}]", app.keymap.label(Screen::Groups, action)),
+            app.theme.key,
+        )
+    };
+    let keys = Line::from(vec![
+        key(Action::Practice),
+        Span::raw(" Practice   "),
+        key(Action::Test),
+        Span::raw(" Test   "),
+        key(Action::Back),
+        Span::raw(" Back"),
+    ]);
+    frame.render_widget(Paragraph::new(keys).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{init_db, queries};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn setup() -> App {
+        let conn = init_db(":memory:").unwrap();
+        for (word, group) in [("abound", 1), ("austere", 1), ("belie", 2)] {
+            queries::insert_word(&conn, word, "", group, "").unwrap();
+        }
+        queries::save_progress(&conn, (Screen::Practice, 1, 1)).unwrap();
+        let mut app = App::new(conn);
+        app.groups = crate::core::stats::group_summaries(&app.conn).unwrap();
+        app.current_screen = Screen::Groups;
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_event(app, KeyEvent::new(code, KeyModifiers::empty()));
+    }
+
+    #[test]
+    fn test_test_starts_chosen_group_and_saves_it() {
+        let mut app = setup();
+
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('t'));
+
+        assert_eq!(app.current_screen, Screen::Test);
+        let session = app.session.as_ref().unwrap();
+        assert_eq!(
+            (session.current().word.as_str(), session.index),
+            ("belie", 0)
+        );
+        assert_eq!(
+            queries::fetch_progress(&app.conn).unwrap(),
+            (Screen::Test, 2, 0)
+        );
+    }
+
+    #[test]
+    fn test_saved_group_resumes_where_it_left_off() {
+        let mut app = setup();
+
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.current_screen, Screen::Practice);
+        assert_eq!(app.session.as_ref().unwrap().current().word, "austere");
+    }
+
+    #[test]
+    fn test_render_lists_groups() {
+        let app = setup();
+
+        let backend = ratatui::backend::TestBackend::new(80, 12);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("> 1        2       0%     -"));
+        assert!(screen.contains(&"░".repeat(BAR_WIDTH)));
+    }
+}
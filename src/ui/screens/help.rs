@@ -0,0 +1,155 @@
+use crate::ui::app::{App, Screen};
+use crate::ui::keymap::{Action, Context, Keymap, keys_label};
+use crate::ui::theme::Theme;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Open or close the help overlay. Returns `true` if the key was used up,
+/// so the screen underneath should not see it.
+pub fn handle_event(app: &mut App, key: KeyEvent) -> bool {
+    if app.show_help {
+        // Any key closes the overlay
+        app.show_help = false;
+        return true;
+    }
+
+    // While typing an answer `?` is just a character
+    let typing =
+        app.current_screen == Screen::Test && app.session.as_ref().is_some_and(|s| s.insert_mode);
+    if !typing && app.keymap.action(app.current_screen, &key) == Some(Action::Help) {
+        app.show_help = true;
+        return true;
+    }
+    false
+}
+
+/// The current screen's key bindings in a box over the screen.
+pub fn render(frame: &mut Frame, app: &App) {
+    render_keys(frame, &app.keymap, app.current_screen.into(), &app.theme);
+}
+
+/// The key bindings of `context` in a box over the screen.
+pub fn render_keys(frame: &mut Frame, keymap: &Keymap, context: Context, theme: &Theme) {
+    let bindings = keymap.bindings(context);
+    let keys: Vec<String> = bindings.iter().map(keys_label).collect();
+    let key_width = keys.iter().map(|k| k.chars().count()).max().unwrap_or(0);
+
+    let mut lines: Vec<Line> = bindings
+        .iter()
+        .zip(&keys)
+        .map(|(binding, keys)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{keys:<key_width$}  "),
+                    theme.key.add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(binding.description),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::styled("Press any key to close", theme.muted));
+
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+    let height = lines.len() as u16 + 2;
+    let area = centered(frame.size(), width, height);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Keys: {} ", title(context))),
+        ),
+        area,
+    );
+}
+
+fn title(context: Context) -> &'static str {
+    match context {
+        Context::Screen(Screen::Menu) => "Main Menu",
+        Context::Screen(Screen::Practice) => "Practice",
+        Context::Screen(Screen::Test) => "Test",
+        Context::Screen(Screen::TutorialPrompt | Screen::Tutorial) => "Tutorial",
+        Context::Screen(Screen::Stats) => "Statistics",
+        Context::Screen(Screen::Groups) => "Choose Group",
+        Context::Screen(Screen::Settings) => "Settings",
+        Context::Checklist => "Extract",
+    }
+}
+
+/// A `width` by `height` box in the middle of `area`, shrunk to fit.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::session::{Session, Type};
+    use crate::db::{init_db, models::Word};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) -> bool {
+        handle_event(app, KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    #[test]
+    fn test_question_mark_toggles_overlay() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+
+        assert!(!press(&mut app, KeyCode::Char('j')));
+        assert!(press(&mut app, KeyCode::Char('?')));
+        assert!(app.show_help);
+        assert!(press(&mut app, KeyCode::Char('j')));
+        assert!(!app.show_help);
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn test_question_mark_is_typed_in_insert_mode() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        let mut session = Session::new(vec![Word::default()], 0, Type::Group);
+        session.insert_mode = true;
+        app.session = Some(session);
+        app.current_screen = Screen::Test;
+
+        assert!(!press(&mut app, KeyCode::Char('?')));
+        assert!(!app.show_help);
+    }
+
+    #[test]
+    fn test_overlay_lists_the_screens_bindings() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.current_screen = Screen::Practice;
+
+        let backend = ratatui::backend::TestBackend::new(80, 20);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Keys: Practice"));
+        assert!(screen.contains("s         Show the definition"));
+        assert!(screen.contains("q/Esc     Back to the menu"));
+        assert!(screen.contains("PageDown  Scroll the definition down"));
+    }
+}
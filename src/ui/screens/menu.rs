@@ -1,27 +1,31 @@
 use crate::audio;
 use crate::core::session;
-use crate::ui::app::{App, MenuAction};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::core::stats::{self, Dashboard};
+use crate::db::queries;
+use crate::ui::app::{App, MenuAction, Screen};
+use crate::ui::keymap::Action;
+use crate::ui::layout::row_at;
+use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
-    style::{Modifier, Style},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    widgets::{Block, Borders, Gauge, List, ListItem, ListState},
 };
+use std::rc::Rc;
 
 pub fn handle_event(app: &mut App, key: KeyEvent) {
     app.error = None;
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => app.should_quit = true,
-        KeyCode::Down | KeyCode::Char('j') => {
+    match app.keymap.action(Screen::Menu, &key) {
+        Some(Action::Back) => app.should_quit = true,
+        Some(Action::Down) => {
             app.next();
             audio::play_menu_sound();
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(Action::Up) => {
             app.previous();
             audio::play_menu_sound();
         }
-        KeyCode::Enter => {
+        Some(Action::Select) => {
             app.select();
 
             match app.menu_items[app.selected] {
@@ -37,8 +41,8 @@ pub fn handle_event(app: &mut App, key: KeyEvent) {
                                     err = "Word list is empty".to_string();
                                 } else {
                                     err = format!(
-                                        "Index {} out of bounds for vector of length {}. Db corrupted",
-                                        session.index,
+                                        "Saved position {} is past the end of the group ({} words). Run `vocabulator doctor --fix`",
+                                        session.index + 1,
                                         session.words.len()
                                     )
                                     .to_string();
@@ -49,6 +53,31 @@ pub fn handle_event(app: &mut App, key: KeyEvent) {
                         Err(e) => app.error = Some(e.to_string()),
                     }
                 }
+                MenuAction::Groups => match stats::group_summaries(&app.conn) {
+                    Ok(groups) if groups.is_empty() => {
+                        app.error = Some("Word list is empty".to_string())
+                    }
+                    Ok(groups) => {
+                        // Start on the group "Continue Learning" would resume
+                        let saved = queries::fetch_progress(&app.conn).map_or(1, |(_, g, _)| g);
+                        app.group_selected =
+                            groups.iter().position(|g| g.group == saved).unwrap_or(0);
+                        app.groups = groups;
+                        app.current_screen = Screen::Groups;
+                    }
+                    Err(e) => app.error = Some(e.to_string()),
+                },
+                MenuAction::Stats => match Dashboard::compute(&app.conn) {
+                    Ok(dashboard) => {
+                        app.dashboard = Some(dashboard);
+                        app.current_screen = Screen::Stats;
+                    }
+                    Err(e) => app.error = Some(e.to_string()),
+                },
+                MenuAction::Settings => {
+                    app.settings_selected = 0;
+                    app.current_screen = Screen::Settings;
+                }
                 MenuAction::RestartTutorial => {
                     // Reset tutorial completion flag
                     use crate::core::tutorial::{reset_tutorial, init_tutorial};
@@ -71,11 +100,40 @@ pub fn handle_event(app: &mut App, key: KeyEvent) {
     }
 }
 
-pub fn render(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+/// Goal header, menu and error line.
+fn layout(app: &App, area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(3)])
-        .split(f.size());
+        .constraints([
+            Constraint::Length(if app.goal.is_some() { 3 } else { 0 }),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(area)
+}
+
+/// Index of the menu item at column `x`, row `y`.
+pub fn item_at(app: &App, area: Rect, x: u16, y: u16) -> Option<usize> {
+    let list = layout(app, area)[1].inner(&Margin::new(1, 1));
+    row_at(list, app.menu_items.len(), app.selected, x, y)
+}
+
+pub fn render(f: &mut Frame, app: &App) {
+    let chunks = layout(app, f.size());
+
+    if let Some(goal) = &app.goal {
+        let (title, style) = if goal.reached() {
+            (" Daily Goal reached! ", app.theme.correct)
+        } else {
+            (" Daily Goal ", app.theme.accent)
+        };
+        let gauge = Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .gauge_style(style)
+            .ratio(goal.ratio())
+            .label(goal.summary());
+        f.render_widget(gauge, chunks[0]);
+    }
 
     let items: Vec<ListItem> = app
         .menu_items
@@ -88,20 +146,20 @@ pub fn render(f: &mut Frame, app: &App) {
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Main Menu"))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_style(app.theme.highlight)
         .highlight_symbol("> ")
         .repeat_highlight_symbol(true);
 
-    f.render_stateful_widget(list, chunks[0], &mut state);
+    f.render_stateful_widget(list, chunks[1], &mut state);
 
     if let Some(err) = &app.error {
         let error_block = Block::default().borders(Borders::ALL).title("Error");
 
         let paragraph = ratatui::widgets::Paragraph::new(err.clone())
             .block(error_block)
-            .style(Style::default().fg(ratatui::style::Color::Red));
+            .style(app.theme.error);
 
-        f.render_widget(paragraph, chunks[1]);
+        f.render_widget(paragraph, chunks[2]);
     }
 }
 
@@ -110,7 +168,7 @@ mod tests {
     use super::*;
     use crate::core::tutorial::{mark_tutorial_completed, is_tutorial_completed};
     use crate::db::schema::INIT_SCHEMA;
-    use crate::ui::app::{App, Screen};
+    use crate::ui::app::App;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use rusqlite::Connection;
 
@@ -213,4 +271,21 @@ mod tests {
         let state = app.tutorial_state.unwrap();
         assert!(state.sample_session.is_some());
     }
+
+    #[test]
+    fn test_statistics_opens_dashboard() {
+        let conn = crate::db::init_db(":memory:").unwrap();
+        let mut app = App::new(conn);
+        app.selected = app
+            .menu_items
+            .iter()
+            .position(|x| *x == MenuAction::Stats)
+            .unwrap();
+
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
+        handle_event(&mut app, key);
+
+        assert_eq!(app.current_screen, Screen::Stats);
+        assert!(app.dashboard.is_some());
+    }
 }
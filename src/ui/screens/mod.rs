@@ -1,5 +1,9 @@
+pub mod groups;
+pub mod help;
 pub mod menu;
 pub mod practice;
+pub mod settings;
+pub mod stats;
 pub mod test;
 pub mod tutorial;
 pub mod tutorial_prompt;
@@ -1,46 +1,58 @@
 use crate::audio;
 use crate::core::{actions, utils};
 use crate::ui::app::{App, Screen};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::keymap::Action;
+use crate::ui::layout::{self, WordLayout};
+use crossterm::event::KeyEvent;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     prelude::*,
-    text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
 };
 
+/// Buttons along the bottom of the screen, left to right.
+pub const BUTTONS: [(&str, Action); 5] = [
+    ("Show", Action::Show),
+    ("Correct", Action::Correct),
+    ("Wrong", Action::Wrong),
+    ("Mark", Action::Mark),
+    ("Next", Action::Next),
+];
+
 pub fn handle_event(app: &mut App, key: KeyEvent) {
     let session = match &mut app.session {
         Some(s) => s,
         None => return,
     };
 
-    match key.code {
-        KeyCode::Char('q') | KeyCode::Esc => {
+    match app.keymap.action(Screen::Practice, &key) {
+        Some(Action::Back) => {
             app.session = None;
             app.current_screen = Screen::Menu;
         }
-        KeyCode::Char('s') => {
+        Some(Action::Show) => {
             session.show_definition = true;
         }
-        KeyCode::Char('y') => {
+        Some(Action::Correct) => {
             if session.show_definition {
                 session.graded = Some(true);
                 audio::play_correct_sound();
             }
         }
-        KeyCode::Char('n') => {
+        Some(Action::Wrong) => {
             if session.show_definition {
                 session.graded = Some(false);
                 audio::play_wrong_sound();
             }
         }
-        KeyCode::Char('m') => {
+        Some(Action::Mark) => {
             let word = session.current_mut();
             word.marked = !word.marked;
             audio::play_mark_sound();
         }
-        KeyCode::Enter => {
+        Some(Action::ScrollUp) => session.scroll.page_up(),
+        Some(Action::ScrollDown) => session.scroll.page_down(),
+        Some(Action::Next) => {
             if session.show_definition && session.graded.is_some() {
                 if let Err(e) = actions::handle_enter(app) {
                     app.error = Some(e.to_string());
@@ -59,25 +71,16 @@ pub fn render(frame: &mut Frame, app: &App) {
     };
 
     let word = session.current();
-    let area = frame.size();
-
-    let layout = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Length(5), // Word
-            Constraint::Length(5), // Definition
-            Constraint::Length(4), // Stats
-            Constraint::Length(5), // Actions
-        ])
-        .split(area);
+    let Some(layout) = WordLayout::new(frame.size(), false) else {
+        layout::render_too_small(frame, false, &app.theme);
+        return;
+    };
 
     // ───────── HEADER ─────────
     let header_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(layout[0]);
+        .split(layout.header);
 
     let left_header = Paragraph::new(format!(
         "{} WORD [{}/{}]",
@@ -104,18 +107,14 @@ pub fn render(frame: &mut Frame, app: &App) {
     frame.render_widget(right_header, header_chunks[1]);
 
     // ───────── WORD ─────────
-    let word_style = match session.graded {
-        Some(true) => Style::default().fg(Color::Green),
-        Some(false) => Style::default().fg(Color::Red),
-        None => Style::default(),
-    };
+    let word_style = app.theme.graded(session.graded);
 
     let word_block = Block::default()
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1));
 
-    let inner = word_block.inner(layout[1]);
-    frame.render_widget(word_block, layout[1]);
+    let inner = word_block.inner(layout.word);
+    frame.render_widget(word_block, layout.word);
 
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -135,73 +134,38 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // ───────── DEFINITION ─────────
     let def_text = if session.show_definition {
-        word.definition.clone()
+        match &word.example {
+            Some(example) => format!("{}\n\"{example}\"", word.definition)
+                .trim()
+                .to_string(),
+            None => word.definition.clone(),
+        }
     } else {
         "(hidden)".into()
     };
 
-    let definition = Paragraph::new(def_text).alignment(Alignment::Center).block(
-        Block::default()
-            .title("Definition")
-            .borders(Borders::ALL)
-            .padding(Padding::horizontal(1)),
-    );
-
-    frame.render_widget(definition, layout[2]);
+    layout::render_definition(frame, layout.definition, &def_text, &session.scroll);
 
     // ───────── STATS ─────────
-    let stats = Paragraph::new(format!(
-        "Last Seen: {}\nAccuracy: {}/{}",
-        utils::relative_time(word.last_seen),
-        word.success_count,
-        word.times_seen
-    ))
-    .block(
-        Block::default()
-            .title("Stats")
-            .borders(Borders::ALL)
-            .padding(Padding::horizontal(1)),
-    );
+    if let Some(area) = layout.stats {
+        let stats = Paragraph::new(format!(
+            "Last Seen: {}\nAccuracy: {}/{}",
+            utils::relative_time(word.last_seen),
+            word.success_count,
+            word.times_seen
+        ))
+        .block(
+            Block::default()
+                .title("Stats")
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1)),
+        );
 
-    frame.render_widget(stats, layout[3]);
+        frame.render_widget(stats, area);
+    }
 
     // ───────── ACTION BUTTONS ─────────
-    let actions_block = Block::default()
-        .title("Actions")
-        .borders(Borders::ALL)
-        .padding(Padding::horizontal(1));
-
-    let inner_actions = actions_block.inner(layout[4]);
-    frame.render_widget(actions_block, layout[4]);
-
-    let buttons = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-        ])
-        .split(inner_actions);
-
-    render_button(frame, buttons[0], "Show", "s");
-    render_button(frame, buttons[1], "Correct", "y");
-    render_button(frame, buttons[2], "Wrong", "n");
-    render_button(frame, buttons[3], "Mark", "m");
-    render_button(frame, buttons[4], "Next", "⏎");
-}
-
-fn render_button(frame: &mut Frame, area: Rect, label: &str, key: &str) {
-    let content = Line::from(vec![
-        Span::styled(label, Style::default().bold()),
-        Span::raw("\n"),
-        Span::styled(format!("[{}]", key), Style::default().fg(Color::Yellow)),
-    ]);
-
-    let button = Paragraph::new(content)
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
-
-    frame.render_widget(button, area);
+    let buttons =
+        BUTTONS.map(|(label, action)| (label, app.keymap.label(Screen::Practice, action)));
+    layout::render_buttons(frame, &layout, &buttons, &app.theme);
 }
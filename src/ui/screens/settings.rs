@@ -0,0 +1,178 @@
+use crate::audio;
+use crate::core::settings::Field;
+use crate::ui::app::{App, Screen};
+use crate::ui::keymap::{Action, Keymap};
+use crate::ui::layout::row_at;
+use crate::ui::theme::Theme;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use std::rc::Rc;
+
+pub fn handle_event(app: &mut App, key: KeyEvent) {
+    app.error = None;
+    let count = Field::ALL.len();
+    match app.keymap.action(Screen::Settings, &key) {
+        Some(Action::Back) => app.current_screen = Screen::Menu,
+        Some(Action::Down) => {
+            app.settings_selected = (app.settings_selected + 1) % count;
+            audio::play_menu_sound();
+        }
+        Some(Action::Up) => {
+            app.settings_selected = (app.settings_selected + count - 1) % count;
+            audio::play_menu_sound();
+        }
+        Some(Action::Right) => change(app, true),
+        Some(Action::Left) => change(app, false),
+        _ => {}
+    }
+}
+
+/// Step the highlighted setting and save it straight away.
+fn change(app: &mut App, forward: bool) {
+    let field = Field::ALL[app.settings_selected];
+    app.settings.cycle(field, forward, &app.config);
+    match field {
+        Field::Sounds => audio::set_enabled(app.settings.sounds),
+        Field::Keys => match Keymap::build(&app.settings.keys, &app.config) {
+            Ok(keymap) => app.keymap = keymap,
+            Err(e) => app.error = Some(e.to_string()),
+        },
+        Field::Theme => match Theme::load(&app.settings.theme, &app.config) {
+            Ok(theme) => app.theme = theme,
+            Err(e) => app.error = Some(e.to_string()),
+        },
+        _ => {}
+    }
+    if let Err(e) = app.settings.save(&app.conn) {
+        app.error = Some(format!("Failed to save settings: {e}"));
+    }
+}
+
+/// Settings list and footer.
+fn layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Settings
+            Constraint::Length(3), // Keys or error
+        ])
+        .split(area)
+}
+
+/// Index of the setting at column `x`, row `y`.
+pub fn item_at(app: &App, area: Rect, x: u16, y: u16) -> Option<usize> {
+    let list = layout(area)[0].inner(&Margin::new(1, 1));
+    row_at(list, Field::ALL.len(), app.settings_selected, x, y)
+}
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let layout = layout(frame.size());
+
+    let items: Vec<ListItem> = Field::ALL
+        .iter()
+        .map(|field| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<24}", field.label())),
+                Span::styled(
+                    format!("< {} >", app.settings.display(*field)),
+                    app.theme.accent,
+                ),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.settings_selected));
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(" Settings "))
+        .highlight_style(app.theme.highlight)
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, layout[0], &mut state);
+
+    render_footer(frame, app, layout[1]);
+}
+
+fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL);
+    if let Some(err) = &app.error {
+        frame.render_widget(
+            Paragraph::new(err.clone())
+                .block(block.title("Error"))
+                .style(app.theme.error),
+            area,
+        );
+        return;
+    }
+
+    let key = |action| {
+        Span::styled(
+            format!("[{}]", app.keymap.label(Screen::Settings, action)),
+            app.theme.key,
+        )
+    };
+    let keys = Line::from(vec![
+        key(Action::Left),
+        Span::raw("/"),
+        key(Action::Right),
+        Span::raw(" Change   "),
+        key(Action::Back),
+        Span::raw(" Back   "),
+        Span::raw("Changes are saved immediately"),
+    ]);
+    frame.render_widget(Paragraph::new(keys).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::settings::Settings;
+    use crate::db::init_db;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_event(app, KeyEvent::new(code, KeyModifiers::empty()));
+    }
+
+    #[test]
+    fn test_changes_are_saved() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.current_screen = Screen::Settings;
+
+        // Session size: whole group -> 10 -> 20 -> 10
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Left);
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.current_screen, Screen::Menu);
+        assert_eq!(app.settings.session_size, 10);
+        assert_eq!(Settings::load(&app.conn).unwrap(), app.settings);
+    }
+
+    #[test]
+    fn test_render_shows_values() {
+        let app = App::new(init_db(":memory:").unwrap());
+
+        let backend = ratatui::backend::TestBackend::new(60, 12);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("Session size            < Whole group >"));
+        assert!(screen.contains("Grading strictness      < normal >"));
+    }
+}
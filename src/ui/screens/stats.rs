@@ -0,0 +1,301 @@
+use crate::core::heatmap::{self, Heatmap};
+use crate::core::stats::{ACTIVITY_DAYS, Dashboard, Mastery, percent};
+use crate::ui::app::{App, Screen};
+use crate::ui::keymap::Action;
+use crate::ui::theme::Theme;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Gauge, Paragraph, Sparkline},
+};
+
+pub fn handle_event(app: &mut App, key: KeyEvent) {
+    match app.keymap.action(Screen::Stats, &key) {
+        Some(Action::Back) => {
+            app.dashboard = None;
+            app.current_screen = Screen::Menu;
+        }
+        Some(Action::Refresh) => {
+            if let Ok(mut dashboard) = Dashboard::compute(&app.conn) {
+                dashboard.show_heatmap = app.dashboard.as_ref().is_some_and(|d| d.show_heatmap);
+                app.dashboard = Some(dashboard);
+            }
+        }
+        Some(Action::TogglePage) => {
+            if let Some(dashboard) = &mut app.dashboard {
+                dashboard.show_heatmap = !dashboard.show_heatmap;
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(dashboard) = &app.dashboard else {
+        return;
+    };
+
+    if dashboard.show_heatmap {
+        render_activity_page(frame, app, dashboard);
+    } else {
+        render_overview_page(frame, app, dashboard);
+    }
+}
+
+fn render_overview_page(frame: &mut Frame, app: &App, dashboard: &Dashboard) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(5), // Mastery
+            Constraint::Min(8),    // Groups and weakest words
+            Constraint::Length(5), // Activity
+            Constraint::Length(3), // Keys
+        ])
+        .split(frame.size());
+
+    render_mastery(frame, &dashboard.mastery, &app.theme, layout[0]);
+
+    let middle = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(layout[1]);
+    render_groups(frame, dashboard, &app.theme, middle[0]);
+    render_weakest(frame, dashboard, &app.theme, middle[1]);
+
+    render_sparkline(frame, dashboard, &app.theme, layout[2]);
+    render_keys(frame, app, "Year heatmap", layout[3]);
+}
+
+fn render_activity_page(frame: &mut Frame, app: &App, dashboard: &Dashboard) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(12), // Heatmap
+            Constraint::Length(5),  // Activity
+            Constraint::Min(0),
+            Constraint::Length(3), // Keys
+        ])
+        .split(frame.size());
+
+    render_heatmap(frame, &dashboard.heatmap, &app.theme, layout[0]);
+    render_sparkline(frame, dashboard, &app.theme, layout[1]);
+    render_keys(frame, app, "Charts", layout[3]);
+}
+
+fn render_sparkline(frame: &mut Frame, dashboard: &Dashboard, theme: &Theme, area: Rect) {
+    let activity = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Reviews per day, last {ACTIVITY_DAYS} days ({} total) ",
+            dashboard.activity.iter().sum::<u64>()
+        )))
+        .data(&dashboard.activity)
+        .style(theme.accent);
+    frame.render_widget(activity, area);
+}
+
+fn render_keys(frame: &mut Frame, app: &App, other_page: &str, area: Rect) {
+    let key = |action| {
+        Span::styled(
+            format!("[{}]", app.keymap.label(Screen::Stats, action)),
+            app.theme.key,
+        )
+    };
+    let keys = Line::from(vec![
+        key(Action::TogglePage),
+        Span::raw(format!(" {other_page}   ")),
+        key(Action::Refresh),
+        Span::raw(" Refresh   "),
+        key(Action::Back),
+        Span::raw(" Back"),
+    ]);
+    frame.render_widget(
+        Paragraph::new(keys).block(Block::default().borders(Borders::ALL)),
+        area,
+    );
+}
+
+/// GitHub-style grid: one column per week, one row per weekday.
+fn render_heatmap(frame: &mut Frame, heatmap: &Heatmap, theme: &Theme, area: Rect) {
+    let mut lines = vec![Line::from(format!("    {}", heatmap.month_row()))];
+    for (weekday, label) in heatmap::WEEKDAY_LABELS.iter().enumerate() {
+        let mut spans = vec![Span::raw(format!("{label:<4}"))];
+        for week in 0..heatmap::WEEKS {
+            if let Some(count) = heatmap.get(week, weekday) {
+                let level = heatmap.level(count);
+                spans.push(Span::styled(
+                    theme.heat_symbols[level].to_string(),
+                    theme.heat[level],
+                ));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let mut legend = vec![Span::raw("    Less ")];
+    for (symbol, style) in theme.heat_symbols.iter().zip(theme.heat) {
+        legend.push(Span::styled(format!("{symbol} "), style));
+    }
+    legend.push(Span::raw("More"));
+    lines.push(Line::from(""));
+    lines.push(Line::from(legend));
+
+    let title = format!(
+        " {} reviews in the last year, on {} days ",
+        heatmap.total(),
+        heatmap.active_days()
+    );
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+fn render_mastery(frame: &mut Frame, mastery: &Mastery, theme: &Theme, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(" Progress ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1); 3])
+        .split(inner);
+
+    let total = mastery.total().max(1) as f64;
+    for (row, (label, count, style)) in rows.iter().zip([
+        ("Mastered", mastery.mastered, theme.correct),
+        ("Learning", mastery.learning, theme.warning),
+        ("Unseen", mastery.unseen, theme.muted),
+    ]) {
+        let gauge = Gauge::default()
+            .ratio(count as f64 / total)
+            .label(format!("{label}: {count}"))
+            .gauge_style(style);
+        frame.render_widget(gauge, *row);
+    }
+}
+
+fn render_groups(frame: &mut Frame, dashboard: &Dashboard, theme: &Theme, area: Rect) {
+    let bars: Vec<Bar> = dashboard
+        .groups
+        .iter()
+        .map(|g| {
+            let accuracy = g.totals.accuracy.unwrap_or(0.0);
+            Bar::default()
+                .value((accuracy * 100.0).round() as u64)
+                .text_value(percent(g.totals.accuracy))
+                .label(Line::from(g.group.to_string()))
+                .style(theme.accuracy(g.totals.accuracy))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Accuracy by group "),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(4)
+        .bar_gap(1)
+        .max(100);
+    frame.render_widget(chart, area);
+}
+
+fn render_weakest(frame: &mut Frame, dashboard: &Dashboard, theme: &Theme, area: Rect) {
+    let lines: Vec<Line> = if dashboard.weakest.is_empty() {
+        vec![Line::from("Nothing reviewed yet")]
+    } else {
+        dashboard
+            .weakest
+            .iter()
+            .map(|w| {
+                Line::from(vec![
+                    Span::raw(format!("{:<18}", w.word)),
+                    Span::styled(
+                        format!("{}/{}", w.success_count, w.times_seen),
+                        theme.accuracy(Some(w.accuracy)),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Weakest words "),
+        ),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_quit_returns_to_menu() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.dashboard = Some(Dashboard::compute(&app.conn).unwrap());
+        app.current_screen = Screen::Stats;
+
+        handle_event(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+
+        assert_eq!(app.current_screen, Screen::Menu);
+        assert!(app.dashboard.is_none());
+    }
+
+    #[test]
+    fn test_render_shows_weakest_words() {
+        let conn = init_db(":memory:").unwrap();
+        crate::db::queries::insert_word(&conn, "austere", "strict", 1, "").unwrap();
+        crate::db::queries::set_review_counts(&conn, "austere", 4, 1).unwrap();
+        let mut app = App::new(conn);
+        app.dashboard = Some(Dashboard::compute(&app.conn).unwrap());
+
+        let backend = ratatui::backend::TestBackend::new(80, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(screen.contains("austere           1/4"));
+        assert!(screen.contains("Learning: 1"));
+    }
+
+    #[test]
+    fn test_h_toggles_heatmap_page() {
+        let mut app = App::new(init_db(":memory:").unwrap());
+        app.dashboard = Some(Dashboard::compute(&app.conn).unwrap());
+
+        handle_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('h'), KeyModifiers::empty()),
+        );
+
+        let backend = ratatui::backend::TestBackend::new(80, 30);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render(f, &app)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(app.dashboard.as_ref().unwrap().show_heatmap);
+        assert!(screen.contains("0 reviews in the last year"));
+    }
+}
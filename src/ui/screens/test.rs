@@ -1,46 +1,69 @@
 use crate::audio;
 use crate::core::{actions, utils};
 use crate::ui::app::{App, Screen};
+use crate::ui::keymap::Action;
+use crate::ui::layout::{self, WordLayout};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout},
     prelude::*,
-    text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph},
 };
 
+/// Buttons along the bottom of the screen, left to right.
+pub const BUTTONS: [(&str, Action); 4] = [
+    ("Insert", Action::Insert),
+    ("Mark", Action::Mark),
+    ("Submit", Action::Submit),
+    ("Quit", Action::Back),
+];
+
 pub fn handle_event(app: &mut App, key: KeyEvent) {
     let session = match &mut app.session {
         Some(s) => s,
         None => return,
     };
 
-    match key.code {
-        KeyCode::Char('q') | KeyCode::Esc if !session.insert_mode => {
+    let keymap = &app.keymap;
+    let action = if session.insert_mode {
+        // Letters are typed rather than looked up
+        [Action::LeaveInsert, Action::Submit, Action::DeleteChar, Action::ScrollUp, Action::ScrollDown]
+            .into_iter()
+            .find(|a| keymap.matches(Screen::Test, *a, &key))
+    } else {
+        keymap.action(Screen::Test, &key)
+    };
+
+    match action {
+        Some(Action::Back) => {
             app.session = None;
             app.current_screen = Screen::Menu;
         }
-        KeyCode::Char('i') if !session.insert_mode => {
+        Some(Action::Insert) => {
             session.insert_mode = true;
         }
-        KeyCode::Esc if session.insert_mode => {
+        Some(Action::LeaveInsert) => {
             session.insert_mode = false;
         }
-        KeyCode::Char(c) if session.insert_mode => {
-            session.input_buffer.push(c);
-        }
-        KeyCode::Backspace if session.insert_mode => {
+        Some(Action::DeleteChar) => {
             session.input_buffer.pop();
         }
-        KeyCode::Char('m') => {
+        Some(Action::ScrollUp) => session.scroll.page_up(),
+        Some(Action::ScrollDown) => session.scroll.page_down(),
+        None if session.insert_mode => {
+            if let KeyCode::Char(c) = key.code {
+                session.input_buffer.push(c);
+            }
+        }
+        Some(Action::Mark) => {
             let word = session.current_mut();
             word.marked = !word.marked;
             audio::play_mark_sound();
         }
-        KeyCode::Enter => {
+        Some(Action::Submit) => {
             if session.graded.is_none() {
                 let word = session.current();
-                let correct = session.input_buffer.trim().eq_ignore_ascii_case(&word.word);
+                let correct = app.settings.strictness.accepts(&session.input_buffer, &word.word);
                 session.graded = Some(correct);
                 session.show_definition = true;
                 session.insert_mode = false;
@@ -69,26 +92,16 @@ pub fn render(frame: &mut Frame, app: &App) {
     };
 
     let word = session.current();
-    let area = frame.size();
-
-    let layout = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Length(5), // Word reveal
-            Constraint::Length(5), // Definition
-            Constraint::Length(3), // Input
-            Constraint::Length(4), // Stats
-            Constraint::Length(5), // Actions
-        ])
-        .split(area);
+    let Some(layout) = WordLayout::new(frame.size(), true) else {
+        layout::render_too_small(frame, true, &app.theme);
+        return;
+    };
 
     // ───────── HEADER ─────────
     let header_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(layout[0]);
+        .split(layout.header);
 
     let left_header = Paragraph::new(format!(
         "{} WORD [{}/{}]",
@@ -121,18 +134,14 @@ pub fn render(frame: &mut Frame, app: &App) {
         "(hidden)".into()
     };
 
-    let style = match session.graded {
-        Some(true) => Style::default().fg(Color::Green),
-        Some(false) => Style::default().fg(Color::Red),
-        None => Style::default(),
-    };
+    let style = app.theme.graded(session.graded);
 
     let word_block = Block::default()
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1));
 
-    let inner = word_block.inner(layout[1]);
-    frame.render_widget(word_block, layout[1]);
+    let inner = word_block.inner(layout.word);
+    frame.render_widget(word_block, layout.word);
 
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -151,20 +160,11 @@ pub fn render(frame: &mut Frame, app: &App) {
     frame.render_widget(word_para, vertical[1]);
 
     // ───────── DEFINITION ─────────
-    let definition = Paragraph::new(word.definition.clone())
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .title("Definition")
-                .borders(Borders::ALL)
-                .padding(Padding::horizontal(1)),
-        );
-
-    frame.render_widget(definition, layout[2]);
+    layout::render_definition(frame, layout.definition, &word.definition, &session.scroll);
 
     // ───────── INPUT ─────────
     let input_style = if session.insert_mode {
-        Style::default().fg(Color::Yellow)
+        app.theme.warning
     } else {
         Style::default()
     };
@@ -178,59 +178,27 @@ pub fn render(frame: &mut Frame, app: &App) {
                 .padding(Padding::horizontal(1)),
         );
 
-    frame.render_widget(input, layout[3]);
+    frame.render_widget(input, layout.input);
 
     // ───────── STATS ─────────
-    let stats = Paragraph::new(format!(
-        "Last Seen: {}\nAccuracy: {}/{}",
-        utils::relative_time(word.last_seen),
-        word.success_count,
-        word.times_seen
-    ))
-    .block(
-        Block::default()
-            .title("Stats")
-            .borders(Borders::ALL)
-            .padding(Padding::horizontal(1)),
-    );
+    if let Some(area) = layout.stats {
+        let stats = Paragraph::new(format!(
+            "Last Seen: {}\nAccuracy: {}/{}",
+            utils::relative_time(word.last_seen),
+            word.success_count,
+            word.times_seen
+        ))
+        .block(
+            Block::default()
+                .title("Stats")
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1)),
+        );
 
-    frame.render_widget(stats, layout[4]);
+        frame.render_widget(stats, area);
+    }
 
     // ───────── ACTION BUTTONS ─────────
-    let actions_block = Block::default()
-        .title("Actions")
-        .borders(Borders::ALL)
-        .padding(Padding::horizontal(1));
-
-    let inner_actions = actions_block.inner(layout[5]);
-    frame.render_widget(actions_block, layout[5]);
-
-    let buttons = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-        ])
-        .split(inner_actions);
-
-    render_button(frame, buttons[0], "Insert", "i");
-    render_button(frame, buttons[1], "Mark", "m");
-    render_button(frame, buttons[2], "Submit", "⏎");
-    render_button(frame, buttons[3], "Quit", "q");
-}
-
-fn render_button(frame: &mut Frame, area: Rect, label: &str, key: &str) {
-    let content = Line::from(vec![
-        Span::styled(label, Style::default().bold()),
-        Span::raw("\n"),
-        Span::styled(format!("[{}]", key), Style::default().fg(Color::Yellow)),
-    ]);
-
-    let button = Paragraph::new(content)
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
-
-    frame.render_widget(button, area);
+    let buttons = BUTTONS.map(|(label, action)| (label, app.keymap.label(Screen::Test, action)));
+    layout::render_buttons(frame, &layout, &buttons, &app.theme);
 }
@@ -6,6 +6,7 @@ use ratatui::prelude::Rect;
 use ratatui::style::Stylize;
 use ratatui::widgets::Padding;
 use crate::ui::app::App;
+use crate::ui::theme::Theme;
 
 /// Render the tutorial screen
 ///
@@ -20,8 +21,6 @@ use crate::ui::app::App;
 ///
 /// **Validates: Requirements 3.2, 3.3, 11.1, 11.2, 11.3, 11.4, 11.5**
 pub fn render(frame: &mut Frame, app: &App) {
-    use crate::core::tutorial::get_current_step;
-
     // Check if tutorial state exists
     let tutorial_state = match &app.tutorial_state {
         Some(state) => state,
@@ -36,17 +35,14 @@ pub fn render(frame: &mut Frame, app: &App) {
         // Check if this is a congratulations dialog
         let is_congrats = tutorial_state.completed_actions.contains(&"SHOW_CONGRATS".to_string());
         if is_congrats {
-            render_congratulations(frame, area);
+            render_congratulations(frame, app, area);
         } else {
-            render_exit_confirmation(frame, area);
+            render_exit_confirmation(frame, app, area);
         }
         return;
     }
 
-    // Determine if we're in a practice step (steps 5-8, 10-12)
-    let is_practice_step = (current_step.id >= 5 && current_step.id <= 8) || (current_step.id >= 10 && current_step.id <= 12);
-
-    if is_practice_step {
+    if is_practice_step(current_step.id) {
         // Render practice screen with tutorial overlay
         render_practice_with_tutorial(frame, app, tutorial_state, current_step);
     } else {
@@ -55,11 +51,73 @@ pub fn render(frame: &mut Frame, app: &App) {
     }
 }
 
+/// Whether step `id` shows the practice screen (steps 5-8, 10-12)
+fn is_practice_step(id: usize) -> bool {
+    (5..=8).contains(&id) || (10..=12).contains(&id)
+}
+
+/// The action buttons of the practice steps, in order
+const BUTTONS: [(&str, Action); 5] = [
+    ("Show", Action::Show),
+    ("Correct", Action::Correct),
+    ("Wrong", Action::Wrong),
+    ("Mark", Action::Mark),
+    ("Next", Action::Next),
+];
+
+/// The action of the button at column `x`, row `y`, if the current step
+/// shows them
+pub fn button_at(app: &App, area: Rect, x: u16, y: u16) -> Option<Action> {
+    let state = app.tutorial_state.as_ref()?;
+    if state.exit_requested || !is_practice_step(get_current_step(state).id) {
+        return None;
+    }
+    let position = ratatui::layout::Position { x, y };
+    button_areas(practice_layout(area)[5])
+        .iter()
+        .position(|button| button.contains(position))
+        .map(|i| BUTTONS[i].1)
+}
+
+/// Tutorial overlay, header, word, definition, stats and actions
+fn practice_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    use ratatui::layout::{Constraint, Direction, Layout};
+
+    Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(6),  // Tutorial overlay
+            Constraint::Length(3),  // Header
+            Constraint::Length(5),  // Word
+            Constraint::Length(5),  // Definition
+            Constraint::Length(4),  // Stats
+            Constraint::Length(5),  // Actions
+        ])
+        .split(area)
+}
+
+/// One slot per button inside the actions panel `area`
+fn button_areas(area: Rect) -> std::rc::Rc<[Rect]> {
+    use ratatui::{
+        layout::{Constraint, Direction, Layout},
+        widgets::{Block, Borders},
+    };
+
+    let inner = Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .inner(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(20); 5])
+        .split(inner)
+}
+
 /// Render exit confirmation dialog
-fn render_exit_confirmation(frame: &mut Frame, area: Rect) {
+fn render_exit_confirmation(frame: &mut Frame, app: &App, area: Rect) {
     use ratatui::{
         layout::Alignment,
-        style::{Color, Style},
         widgets::{Block, Borders, Clear, Paragraph},
     };
 
@@ -73,7 +131,7 @@ fn render_exit_confirmation(frame: &mut Frame, area: Rect) {
     let block = Block::default()
         .title("Exit Tutorial?")
         .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
+        .style(app.theme.popup);
 
     let text = vec![
         "",
@@ -83,23 +141,23 @@ fn render_exit_confirmation(frame: &mut Frame, area: Rect) {
         "restart the tutorial anytime from the main menu.",
         "",
         "",
-        "Press 'y' to exit and start learning",
-        "Press 'n' or Escape to continue tutorial",
+        "Press {confirm} to exit and start learning",
+        "Press {cancel} to continue tutorial",
     ];
 
-    let paragraph = Paragraph::new(text.join("\n"))
+    let paragraph = Paragraph::new(fill_keys(&text.join("\n"), &app.keymap))
         .block(block)
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::White));
+        .style(app.theme.text);
 
     frame.render_widget(paragraph, popup_area);
 }
 
 /// Render congratulations dialog
-fn render_congratulations(frame: &mut Frame, area: Rect) {
+fn render_congratulations(frame: &mut Frame, app: &App, area: Rect) {
     use ratatui::{
         layout::Alignment,
-        style::{Color, Modifier, Style},
+        style::Modifier,
         widgets::{Block, Borders, Clear, Paragraph},
     };
 
@@ -113,20 +171,20 @@ fn render_congratulations(frame: &mut Frame, area: Rect) {
     let block = Block::default()
         .title("🎉 Congratulations! 🎉")
         .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black).fg(Color::Green));
+        .style(app.theme.popup.patch(app.theme.correct));
 
     let text = vec![
         "",
         "You've completed the tutorial!",
         "",
         "You now know how to:",
-        "• Navigate menus with arrow keys or j/k",
+        "• Navigate menus with {up} and {down}",
         "• Practice vocabulary words",
-        "• Show definitions with 's'",
-        "• Grade yourself with 'y' or 'n'",
-        "• Bookmark words with 'm'",
-        "• Move to the next word with Enter",
-        "• Exit practice with 'q' or Escape",
+        "• Show definitions with {show}",
+        "• Grade yourself with {correct} or {wrong}",
+        "• Bookmark words with {mark}",
+        "• Move to the next word with {next}",
+        "• Exit practice with {back}",
         "",
         "There's also a Test mode where you type the word!",
         "Your progress auto-saves, so practice anytime!",
@@ -135,10 +193,10 @@ fn render_congratulations(frame: &mut Frame, area: Rect) {
         "Press any key to start practicing!",
     ];
 
-    let paragraph = Paragraph::new(text.join("\n"))
+    let paragraph = Paragraph::new(fill_keys(&text.join("\n"), &app.keymap))
         .block(block)
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD));
+        .style(app.theme.text.add_modifier(Modifier::BOLD));
 
     frame.render_widget(paragraph, popup_area);
 }
@@ -153,7 +211,6 @@ fn render_tutorial_only(
 ) {
     use ratatui::{
         layout::{Alignment, Constraint, Direction, Layout},
-        style::{Color, Style},
         widgets::{Block, Borders, Padding, Paragraph},
     };
 
@@ -176,7 +233,7 @@ fn render_tutorial_only(
     );
     let progress = Paragraph::new(progress_text)
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::Cyan))
+        .style(app.theme.accent)
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -185,15 +242,15 @@ fn render_tutorial_only(
     frame.render_widget(progress, layout[0]);
 
     // ───────── INSTRUCTION ─────────
-    let instruction = Paragraph::new(current_step.instruction)
+    let instruction = Paragraph::new(current_step.instruction_text(&app.keymap))
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::White))
+        .style(app.theme.text)
         .block(
             Block::default()
                 .title("Instructions")
                 .borders(Borders::ALL)
                 .padding(Padding::horizontal(1))
-                .style(Style::default().fg(Color::Yellow)),
+                .style(app.theme.warning),
         );
     frame.render_widget(instruction, layout[1]);
 
@@ -216,7 +273,7 @@ fn render_tutorial_only(
 
         let content = Paragraph::new(message)
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White))
+            .style(app.theme.text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -228,12 +285,12 @@ fn render_tutorial_only(
         let message = if current_step.id == 0 {
             "Welcome to Vocabulator!\n\nThis interactive tutorial will guide you through\nall the features of the application.\n\nYou'll learn by doing - the tutorial will\nwait for you to perform each action correctly."
         } else {
-            "Congratulations!\n\nYou've completed the tutorial and learned:\n\n• How to navigate menus\n• How to practice vocabulary words\n• How to grade yourself\n• How to bookmark words\n• How to exit and return to the menu\n\nThere's also a Test mode where you type the word!\nYour progress auto-saves, so feel free to\nquit anytime with 'q' or Escape."
+            "Congratulations!\n\nYou've completed the tutorial and learned:\n\n• How to navigate menus\n• How to practice vocabulary words\n• How to grade yourself\n• How to bookmark words\n• How to exit and return to the menu\n\nThere's also a Test mode where you type the word!\nYour progress auto-saves, so feel free to\nquit anytime with {back}."
         };
 
-        let content = Paragraph::new(message)
+        let content = Paragraph::new(fill_keys(message, &app.keymap))
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::White))
+            .style(app.theme.text)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -246,7 +303,7 @@ fn render_tutorial_only(
     if let Some(error) = &app.error {
         let hint = Paragraph::new(error.as_str())
             .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::Red))
+            .style(app.theme.error)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -265,7 +322,6 @@ fn render_practice_with_tutorial(
 ) {
     use ratatui::{
         layout::{Alignment, Constraint, Direction, Layout},
-        style::{Color, Style},
         widgets::{Block, Borders, Padding, Paragraph},
     };
     use crate::core::utils;
@@ -280,18 +336,7 @@ fn render_practice_with_tutorial(
     let area = frame.size();
 
     // Main layout with tutorial overlay at top
-    let main_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(1)
-        .constraints([
-            Constraint::Length(6),  // Tutorial overlay
-            Constraint::Length(3),  // Header
-            Constraint::Length(5),  // Word
-            Constraint::Length(5),  // Definition
-            Constraint::Length(4),  // Stats
-            Constraint::Length(5),  // Actions
-        ])
-        .split(area);
+    let main_layout = practice_layout(area);
 
     // ───────── TUTORIAL OVERLAY ─────────
     let tutorial_layout = Layout::default()
@@ -310,7 +355,7 @@ fn render_practice_with_tutorial(
     );
     let progress = Paragraph::new(progress_text)
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::Cyan))
+        .style(app.theme.accent)
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -322,13 +367,13 @@ fn render_practice_with_tutorial(
     let instruction_text = if let Some(error) = &app.error {
         format!("❌ {}", error)
     } else {
-        format!("📖 {}", current_step.instruction)
+        format!("📖 {}", current_step.instruction_text(&app.keymap))
     };
 
     let instruction_style = if app.error.is_some() {
-        Style::default().fg(Color::Red)
+        app.theme.error
     } else {
-        Style::default().fg(Color::Yellow)
+        app.theme.warning
     };
 
     let instruction = Paragraph::new(instruction_text)
@@ -374,11 +419,7 @@ fn render_practice_with_tutorial(
     frame.render_widget(right_header, header_chunks[1]);
 
     // Word
-    let word_style = match session.graded {
-        Some(true) => Style::default().fg(Color::Green),
-        Some(false) => Style::default().fg(Color::Red),
-        None => Style::default(),
-    };
+    let word_style = app.theme.graded(session.graded);
 
     let word_block = Block::default()
         .borders(Borders::ALL)
@@ -440,6 +481,7 @@ fn render_practice_with_tutorial(
         frame,
         main_layout[5],
         current_step.highlight.as_ref(),
+        app,
     );
 }
 
@@ -448,44 +490,28 @@ fn render_actions_with_highlight(
     frame: &mut Frame,
     area: Rect,
     highlight: Option<&crate::core::tutorial::HighlightTarget>,
+    app: &App,
 ) {
-    use ratatui::{
-        layout::{Constraint, Direction, Layout},
-        widgets::{Block, Borders},
-    };
+    use ratatui::widgets::{Block, Borders};
     use crate::core::tutorial::HighlightTarget;
 
     let actions_block = Block::default()
         .title("Actions")
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1));
-
-    let inner_actions = actions_block.inner(area);
     frame.render_widget(actions_block, area);
 
-    let buttons = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-            Constraint::Percentage(20),
-        ])
-        .split(inner_actions);
-
-    // Determine which button to highlight
-    let highlight_key = if let Some(HighlightTarget::KeyHint(key)) = highlight {
-        Some(*key)
+    // Determine which buttons to highlight
+    let highlighted: &[Action] = if let Some(HighlightTarget::KeyHint(actions)) = highlight {
+        actions
     } else {
-        None
+        &[]
     };
 
-    render_button_with_highlight(frame, buttons[0], "Show", "s", highlight_key);
-    render_button_with_highlight(frame, buttons[1], "Correct", "y", highlight_key);
-    render_button_with_highlight(frame, buttons[2], "Wrong", "n", highlight_key);
-    render_button_with_highlight(frame, buttons[3], "Mark", "m", highlight_key);
-    render_button_with_highlight(frame, buttons[4], "Next", "⏎", highlight_key);
+    for ((label, action), area) in BUTTONS.into_iter().zip(button_areas(area).iter()) {
+        let key = app.keymap.label(Screen::Tutorial, action);
+        render_button_with_highlight(frame, *area, label, &key, highlighted.contains(&action), &app.theme);
+    }
 }
 
 /// Render a single button with optional highlighting
@@ -494,29 +520,24 @@ fn render_button_with_highlight(
     area: Rect,
     label: &str,
     key: &str,
-    highlight_key: Option<&str>,
+    should_highlight: bool,
+    theme: &Theme,
 ) {
     use ratatui::{
         layout::Alignment,
-        style::{Color, Style},
+        style::Style,
         text::{Line, Span},
         widgets::{Block, Borders, Paragraph},
     };
 
-    // Check if this button should be highlighted
-    let should_highlight = highlight_key.map_or(false, |h| {
-        // Exact match or special case for Enter key
-        h == key || (key == "⏎" && (h == "Enter" || h.to_lowercase() == "enter"))
-    });
-
     let key_style = if should_highlight {
-        Style::default().fg(Color::Green).bold()
+        theme.correct.bold()
     } else {
-        Style::default().fg(Color::Yellow)
+        theme.key
     };
 
     let border_style = if should_highlight {
-        Style::default().fg(Color::Green)
+        theme.correct
     } else {
         Style::default()
     };
@@ -542,7 +563,6 @@ fn render_menu_preview(
     highlight: Option<&crate::core::tutorial::HighlightTarget>,
 ) {
     use ratatui::{
-        style::{Color, Style},
         text::Line,
         widgets::{Block, Borders, List, ListItem, Padding},
     };
@@ -559,7 +579,7 @@ fn render_menu_preview(
         .title("Main Menu")
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1))
-        .style(Style::default().fg(Color::White));
+        .style(app.theme.text);
 
     let inner = menu_block.inner(area);
     frame.render_widget(menu_block, area);
@@ -577,11 +597,11 @@ fn render_menu_preview(
             let text = format!("{}{}", prefix, action.label());
 
             let style = if is_highlighted {
-                Style::default().fg(Color::Green).bold()
+                app.theme.correct.bold()
             } else if is_selected {
-                Style::default().fg(Color::Yellow)
+                app.theme.warning
             } else {
-                Style::default().fg(Color::White)
+                app.theme.text
             };
 
             ListItem::new(Line::from(text)).style(style)
@@ -615,9 +635,10 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-use crossterm::event::{KeyCode, KeyEvent};
-use crate::core::tutorial::{validate_and_advance, ValidationResult, mark_tutorial_completed};
+use crossterm::event::KeyEvent;
+use crate::core::tutorial::{fill_keys, get_current_step, validate_and_advance, StepValidation, ValidationResult, mark_tutorial_completed};
 use crate::ui::app::Screen;
+use crate::ui::keymap::Action;
 
 /// Handle keyboard events for the tutorial screen
 ///
@@ -654,34 +675,29 @@ pub fn handle_event(app: &mut App, key: KeyEvent) {
         }
         
         // Handle exit confirmation
-        match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') => {
-                // Confirm exit - return to menu without marking tutorial as completed
-                app.tutorial_state = None;
-                app.current_screen = Screen::Menu;
-                return;
-            }
-            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                // Cancel exit - resume tutorial
-                app.tutorial_state.as_mut().unwrap().exit_requested = false;
-                return;
-            }
-            _ => {
-                // Ignore other keys during confirmation
-                return;
-            }
+        if app.keymap.matches(Screen::Tutorial, Action::Confirm, &key) {
+            // Confirm exit - return to menu without marking tutorial as completed
+            app.tutorial_state = None;
+            app.current_screen = Screen::Menu;
+        } else if app.keymap.matches(Screen::Tutorial, Action::Cancel, &key) {
+            // Cancel exit - resume tutorial
+            app.tutorial_state.as_mut().unwrap().exit_requested = false;
         }
+        // Ignore other keys during confirmation
+        return;
     }
 
-    // Check for exit request (q or Escape)
-    if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+    // Check for exit request, unless this step asks for the same key
+    let current_step = app.tutorial_state.as_ref().unwrap().current_step;
+    let step = get_current_step(app.tutorial_state.as_ref().unwrap());
+    let expects_back = matches!(step.validation, StepValidation::KeyPress(actions) if actions.contains(&Action::Back));
+    if app.keymap.matches(Screen::Tutorial, Action::Back, &key) && !expects_back {
         // Show confirmation prompt
         app.tutorial_state.as_mut().unwrap().exit_requested = true;
         return;
     }
 
     // Special handling for step 4 (auto-advance on any key)
-    let current_step = app.tutorial_state.as_ref().unwrap().current_step;
     if current_step == 4 {
         // Any key press advances from step 4 to step 5
         app.tutorial_state.as_mut().unwrap().current_step = 5;
@@ -692,82 +708,61 @@ pub fn handle_event(app: &mut App, key: KeyEvent) {
 
     // Handle menu navigation keys during tutorial steps 1-3
     if current_step >= 1 && current_step <= 3 {
-        match key.code {
-            KeyCode::Down | KeyCode::Char('j') => {
-                // Move menu selection down
-                if app.selected < app.menu_items.len() - 1 {
-                    app.selected += 1;
-                } else {
-                    app.selected = 0; // Wrap around
-                }
+        if app.keymap.matches(Screen::Tutorial, Action::Down, &key) {
+            // Move menu selection down
+            if app.selected < app.menu_items.len() - 1 {
+                app.selected += 1;
+            } else {
+                app.selected = 0; // Wrap around
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                // Move menu selection up
-                if app.selected > 0 {
-                    app.selected -= 1;
-                } else {
-                    app.selected = app.menu_items.len() - 1; // Wrap around
-                }
+        } else if app.keymap.matches(Screen::Tutorial, Action::Up, &key) {
+            // Move menu selection up
+            if app.selected > 0 {
+                app.selected -= 1;
+            } else {
+                app.selected = app.menu_items.len() - 1; // Wrap around
             }
-            _ => {}
         }
     }
 
     // Handle practice-related keys during tutorial (steps 5-8, 10-12)
     if (current_step >= 5 && current_step <= 8) || (current_step >= 10 && current_step <= 12) {
         // Handle keys that modify the sample session
-        match key.code {
-            KeyCode::Char('m') => {
-                // Toggle bookmark on current word in sample session
-                if let Some(ref mut tutorial_state) = app.tutorial_state {
-                    if let Some(ref mut session) = tutorial_state.sample_session {
-                        if session.index < session.words.len() {
-                            session.words[session.index].marked = !session.words[session.index].marked;
-                            crate::audio::play_mark_sound();
-                        }
-                    }
+        let action = [Action::Mark, Action::Show, Action::Correct, Action::Wrong, Action::Next]
+            .into_iter()
+            .find(|action| app.keymap.matches(Screen::Tutorial, *action, &key));
+        if let Some(ref mut tutorial_state) = app.tutorial_state
+            && let Some(ref mut session) = tutorial_state.sample_session
+        {
+            match action {
+                Some(Action::Mark) if session.index < session.words.len() => {
+                    // Toggle bookmark on current word in sample session
+                    session.words[session.index].marked = !session.words[session.index].marked;
+                    crate::audio::play_mark_sound();
                 }
-            }
-            KeyCode::Char('s') => {
-                // Show definition in sample session
-                if let Some(ref mut tutorial_state) = app.tutorial_state {
-                    if let Some(ref mut session) = tutorial_state.sample_session {
-                        session.show_definition = true;
-                    }
+                Some(Action::Show) => {
+                    // Show definition in sample session
+                    session.show_definition = true;
                 }
-            }
-            KeyCode::Char('y') => {
-                // Grade the word as correct in sample session
-                if let Some(ref mut tutorial_state) = app.tutorial_state {
-                    if let Some(ref mut session) = tutorial_state.sample_session {
-                        session.graded = Some(true);
-                        crate::audio::play_correct_sound();
-                    }
+                Some(Action::Correct) => {
+                    // Grade the word as correct in sample session
+                    session.graded = Some(true);
+                    crate::audio::play_correct_sound();
                 }
-            }
-            KeyCode::Char('n') => {
-                // Grade the word as incorrect in sample session
-                if let Some(ref mut tutorial_state) = app.tutorial_state {
-                    if let Some(ref mut session) = tutorial_state.sample_session {
-                        session.graded = Some(false);
-                        crate::audio::play_wrong_sound();
-                    }
+                Some(Action::Wrong) => {
+                    // Grade the word as incorrect in sample session
+                    session.graded = Some(false);
+                    crate::audio::play_wrong_sound();
                 }
-            }
-            KeyCode::Enter => {
-                // Advance to next word in sample session
-                if let Some(ref mut tutorial_state) = app.tutorial_state {
-                    if let Some(ref mut session) = tutorial_state.sample_session {
-                        if session.index < session.words.len() - 1 {
-                            session.index += 1;
-                            // Reset state for new word
-                            session.show_definition = false;
-                            session.graded = None;
-                        }
-                    }
+                Some(Action::Next) if session.index < session.words.len() - 1 => {
+                    // Advance to next word in sample session
+                    session.index += 1;
+                    // Reset state for new word
+                    session.show_definition = false;
+                    session.graded = None;
                 }
+                _ => {}
             }
-            _ => {}
         }
     }
 
@@ -812,7 +807,7 @@ mod tests {
     use super::*;
     use crate::ui::app::App;
     use crate::core::tutorial::init_tutorial;
-    use crossterm::event::{KeyEvent, KeyModifiers};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
     #[test]
     fn test_handle_event_no_tutorial_state_returns_to_menu() {
@@ -1090,4 +1085,67 @@ mod tests {
         // Verify we're now at word index 1
         assert_eq!(app.tutorial_state.as_ref().unwrap().sample_session.as_ref().unwrap().index, 1);
     }
+
+    #[test]
+    fn test_handle_event_follows_configured_keys() {
+        use crate::config::Config;
+        use crate::ui::keymap::Keymap;
+
+        let mut app = App::new_test();
+        app.current_screen = Screen::Tutorial;
+        let config = Config::parse("test.conf", "[keys]\nmark = b\n").unwrap();
+        app.keymap = Keymap::build("default", &config).unwrap();
+        let mut state = init_tutorial();
+        state.current_step = 7;
+        app.tutorial_state = Some(state);
+
+        // The default key no longer marks the word
+        handle_event(&mut app, KeyEvent::new(KeyCode::Char('m'), KeyModifiers::empty()));
+        assert!(!app.tutorial_state.as_ref().unwrap().sample_session.as_ref().unwrap().words[0].marked);
+
+        handle_event(&mut app, KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert!(app.tutorial_state.as_ref().unwrap().sample_session.as_ref().unwrap().words[0].marked);
+        assert_eq!(app.tutorial_state.as_ref().unwrap().current_step, 8);
+    }
+
+    #[test]
+    fn test_handle_event_arrows_only_preset() {
+        use crate::ui::keymap::Keymap;
+
+        let mut app = App::new_test();
+        app.current_screen = Screen::Tutorial;
+        app.keymap = Keymap::preset("arrows-only").unwrap();
+        let mut state = init_tutorial();
+        state.current_step = 5;
+        app.tutorial_state = Some(state);
+
+        // 's' is not bound, Space shows the definition
+        handle_event(&mut app, KeyEvent::new(KeyCode::Char('s'), KeyModifiers::empty()));
+        assert_eq!(app.tutorial_state.as_ref().unwrap().current_step, 5);
+        assert!(app.error.as_ref().unwrap().contains("Space"));
+
+        handle_event(&mut app, KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        assert_eq!(app.tutorial_state.as_ref().unwrap().current_step, 6);
+        assert!(app.tutorial_state.as_ref().unwrap().sample_session.as_ref().unwrap().show_definition);
+
+        // 'y' does not grade, Right does
+        handle_event(&mut app, KeyEvent::new(KeyCode::Char('y'), KeyModifiers::empty()));
+        assert_eq!(app.tutorial_state.as_ref().unwrap().current_step, 6);
+        handle_event(&mut app, KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        assert_eq!(app.tutorial_state.as_ref().unwrap().current_step, 7);
+    }
+
+    #[test]
+    fn test_handle_event_back_completes_exit_step() {
+        let mut app = App::new_test();
+        app.current_screen = Screen::Tutorial;
+        let mut state = init_tutorial();
+        state.current_step = 12;
+        app.tutorial_state = Some(state);
+
+        handle_event(&mut app, KeyEvent::new(KeyCode::Char('q'), KeyModifiers::empty()));
+
+        assert!(!app.tutorial_state.as_ref().unwrap().exit_requested);
+        assert_eq!(app.tutorial_state.as_ref().unwrap().current_step, 13);
+    }
 }
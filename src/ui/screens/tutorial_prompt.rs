@@ -3,11 +3,19 @@
 
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
-    style::{Modifier, Style},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     widgets::{Block, Borders, List, ListItem, ListState},
 };
 use crate::ui::app::App;
+use crate::ui::layout::row_at;
+
+/// The two choices, in order
+const OPTIONS: [&str; 2] = ["Start Tutorial", "Skip to Main Menu"];
+
+/// Index of the option at column `x`, row `y`
+pub fn item_at(app: &App, area: Rect, x: u16, y: u16) -> Option<usize> {
+    row_at(area.inner(&Margin::new(1, 1)), OPTIONS.len(), app.selected, x, y)
+}
 
 /// Render the tutorial prompt screen
 ///
@@ -25,8 +33,7 @@ pub fn render(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Min(0)])
         .split(frame.size());
 
-    let options = vec!["Start Tutorial", "Skip to Main Menu"];
-    let items: Vec<ListItem> = options
+    let items: Vec<ListItem> = OPTIONS
         .iter()
         .map(|option| ListItem::new(*option))
         .collect();
@@ -36,14 +43,15 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Welcome to Vocabulator"))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_style(app.theme.highlight)
         .highlight_symbol("> ")
         .repeat_highlight_symbol(true);
 
     frame.render_stateful_widget(list, chunks[0], &mut state);
 }
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::KeyEvent;
+use crate::ui::keymap::Action;
 use crate::core::tutorial::init_tutorial;
 use crate::ui::app::Screen;
 use crate::audio;
@@ -61,19 +69,19 @@ use crate::audio;
 ///
 /// **Validates: Requirements 1.2, 1.3, 1.4**
 pub fn handle_event(app: &mut App, key: KeyEvent) {
-    match key.code {
+    match app.keymap.action(Screen::TutorialPrompt, &key) {
         // Navigate down
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(Action::Down) => {
             audio::play_menu_sound();
             app.selected = (app.selected + 1) % 2; // Wrap between 0 and 1
         }
         // Navigate up
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(Action::Up) => {
             audio::play_menu_sound();
             app.selected = if app.selected == 0 { 1 } else { 0 }; // Wrap between 0 and 1
         }
         // Select option
-        KeyCode::Enter => {
+        Some(Action::Select) => {
             match app.selected {
                 0 => {
                     // Start Tutorial selected
@@ -0,0 +1,337 @@
+// Themes
+// The colours every screen draws with: built-in themes, user themes from the
+// config file, and plain text styles when NO_COLOR is set
+
+use crate::config::Config;
+use crate::core::heatmap::LEVEL_CHARS;
+use anyhow::Result;
+use ratatui::style::{Color, Modifier, Style};
+use std::str::FromStr;
+
+/// Names of the themes that are always available, in settings order.
+pub const BUILT_IN: [&str; 4] = ["default", "high-contrast", "colorblind", "monochrome"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Body text of dialogs and instructions
+    pub text: Style,
+    /// Titles, values and other things to look at
+    pub accent: Style,
+    /// A right answer, mastered words
+    pub correct: Style,
+    /// A wrong answer
+    pub wrong: Style,
+    /// Error messages
+    pub error: Style,
+    /// Hints, words still being learned
+    pub warning: Style,
+    /// Unseen words and other quiet text
+    pub muted: Style,
+    /// Key names on buttons and in the help overlay
+    pub key: Style,
+    /// The selected row of a list
+    pub highlight: Style,
+    /// Background of popup dialogs
+    pub popup: Style,
+    /// Heatmap cells for activity levels 0 to 4
+    pub heat: [Style; 5],
+    pub heat_symbols: [char; 5],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let fg = |color| Style::default().fg(color);
+        Self {
+            text: fg(Color::White),
+            accent: fg(Color::Cyan),
+            correct: fg(Color::Green),
+            wrong: fg(Color::Red),
+            error: fg(Color::Red),
+            warning: fg(Color::Yellow),
+            muted: fg(Color::DarkGray),
+            key: fg(Color::Yellow),
+            highlight: Style::default().add_modifier(Modifier::BOLD),
+            popup: Style::default().bg(Color::Black),
+            // As on GitHub
+            heat: [
+                fg(Color::DarkGray),
+                fg(Color::Rgb(14, 68, 41)),
+                fg(Color::Rgb(0, 109, 50)),
+                fg(Color::Rgb(38, 166, 65)),
+                fg(Color::Rgb(57, 211, 83)),
+            ],
+            heat_symbols: ['■'; 5],
+        }
+    }
+}
+
+impl Theme {
+    pub fn built_in(name: &str) -> Option<Self> {
+        let fg = |color| Style::default().fg(color);
+        let bold = |color| fg(color).add_modifier(Modifier::BOLD);
+        match name {
+            "default" => Some(Self::default()),
+            "high-contrast" => Some(Self {
+                text: bold(Color::White),
+                accent: bold(Color::LightCyan),
+                correct: bold(Color::LightGreen),
+                wrong: bold(Color::LightRed),
+                error: bold(Color::LightRed),
+                warning: bold(Color::LightYellow),
+                muted: fg(Color::Gray),
+                key: bold(Color::LightYellow),
+                highlight: Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+                popup: Style::default().bg(Color::Black),
+                heat: [
+                    fg(Color::Gray),
+                    bold(Color::White),
+                    bold(Color::White),
+                    bold(Color::White),
+                    bold(Color::White),
+                ],
+                heat_symbols: LEVEL_CHARS,
+            }),
+            // Okabe-Ito colours, told apart with any kind of colour blindness
+            "colorblind" => Some(Self {
+                accent: fg(Color::Rgb(86, 180, 233)),
+                correct: fg(Color::Rgb(0, 114, 178)),
+                wrong: fg(Color::Rgb(213, 94, 0)),
+                error: fg(Color::Rgb(213, 94, 0)),
+                warning: fg(Color::Rgb(230, 159, 0)),
+                key: fg(Color::Rgb(240, 228, 66)),
+                heat: [
+                    fg(Color::DarkGray),
+                    fg(Color::Rgb(8, 48, 107)),
+                    fg(Color::Rgb(33, 113, 181)),
+                    fg(Color::Rgb(107, 174, 214)),
+                    fg(Color::Rgb(198, 219, 239)),
+                ],
+                ..Self::default()
+            }),
+            // Text attributes only, for NO_COLOR
+            "monochrome" => Some(Self {
+                text: Style::default(),
+                accent: Style::default(),
+                correct: Style::default().add_modifier(Modifier::BOLD),
+                wrong: Style::default().add_modifier(Modifier::CROSSED_OUT),
+                error: Style::default().add_modifier(Modifier::BOLD),
+                warning: Style::default().add_modifier(Modifier::ITALIC),
+                muted: Style::default().add_modifier(Modifier::DIM),
+                key: Style::default().add_modifier(Modifier::BOLD),
+                highlight: Style::default().add_modifier(Modifier::REVERSED),
+                popup: Style::default(),
+                heat: [Style::default(); 5],
+                heat_symbols: LEVEL_CHARS,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Built-in themes followed by the `[theme.NAME]` sections of the config.
+    pub fn names(config: &Config) -> Vec<&str> {
+        let mut names = BUILT_IN.to_vec();
+        for name in config.section_names() {
+            if let Some(name) = name.strip_prefix("theme.")
+                && !names.contains(&name)
+            {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// The theme called `name`, or `monochrome` when the NO_COLOR environment
+    /// variable is set. Unknown names fall back to the default theme.
+    pub fn load(name: &str, config: &Config) -> Result<Self> {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        Self::pick(name, config, no_color)
+    }
+
+    fn pick(name: &str, config: &Config, no_color: bool) -> Result<Self> {
+        if no_color {
+            return Ok(Self::built_in("monochrome").unwrap_or_default());
+        }
+        if let Some(theme) = Self::built_in(name) {
+            return Ok(theme);
+        }
+
+        // A user theme starts from `base` and overrides single styles
+        let section = format!("theme.{name}");
+        let mut theme = Self::default();
+        if let Some(entry) = config.section(&section).find(|e| e.key == "base") {
+            let Some(base) = Self::built_in(&entry.value) else {
+                return Err(config.error(entry, format!("unknown base theme `{}`", entry.value)));
+            };
+            theme = base;
+        }
+        for entry in config.section(&section) {
+            if entry.key == "base" {
+                continue;
+            }
+            if entry.key == "heat" {
+                let styles = entry
+                    .value
+                    .split(',')
+                    .map(|s| parse_style(s.trim()))
+                    .collect::<Option<Vec<_>>>();
+                let Some(styles) = styles.and_then(|s| <[Style; 5]>::try_from(s).ok()) else {
+                    return Err(config.error(entry, "`heat` needs five comma-separated styles"));
+                };
+                theme.heat = styles;
+                continue;
+            }
+            let Some(style) = parse_style(&entry.value) else {
+                return Err(config.error(entry, format!("bad style `{}`", entry.value)));
+            };
+            let slot = match entry.key.as_str() {
+                "text" => &mut theme.text,
+                "accent" => &mut theme.accent,
+                "correct" => &mut theme.correct,
+                "wrong" => &mut theme.wrong,
+                "error" => &mut theme.error,
+                "warning" => &mut theme.warning,
+                "muted" => &mut theme.muted,
+                "key" => &mut theme.key,
+                "highlight" => &mut theme.highlight,
+                "popup" => &mut theme.popup,
+                key => return Err(config.error(entry, format!("unknown theme style `{key}`"))),
+            };
+            *slot = style;
+        }
+        Ok(theme)
+    }
+
+    /// Style of a graded answer, plain before grading.
+    pub fn graded(&self, correct: Option<bool>) -> Style {
+        match correct {
+            Some(true) => self.correct,
+            Some(false) => self.wrong,
+            None => Style::default(),
+        }
+    }
+
+    /// Good, middling or poor accuracy; muted when nothing was reviewed.
+    pub fn accuracy(&self, accuracy: Option<f64>) -> Style {
+        match accuracy {
+            None => self.muted,
+            Some(a) if a >= 0.8 => self.correct,
+            Some(a) if a >= 0.5 => self.warning,
+            Some(_) => self.wrong,
+        }
+    }
+}
+
+/// A style such as `bold light-red`, `#ff8800 underlined` or `black on white`:
+/// a foreground colour, `on` and a background colour, and modifiers.
+fn parse_style(s: &str) -> Option<Style> {
+    let mut style = Style::default();
+    let mut words = s.split_whitespace().peekable();
+    words.peek()?;
+    while let Some(word) = words.next() {
+        style = match word {
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "dim" => style.add_modifier(Modifier::DIM),
+            "italic" => style.add_modifier(Modifier::ITALIC),
+            "underlined" => style.add_modifier(Modifier::UNDERLINED),
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            "crossed-out" => style.add_modifier(Modifier::CROSSED_OUT),
+            "plain" => style,
+            "on" => style.bg(Color::from_str(words.next()?).ok()?),
+            color => style.fg(Color::from_str(color).ok()?),
+        };
+    }
+    Some(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_themes() {
+        for name in BUILT_IN {
+            assert!(Theme::built_in(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::names(&Config::default()), BUILT_IN);
+        assert!(Theme::built_in("nope").is_none());
+    }
+
+    #[test]
+    fn test_no_color_wins() {
+        let config = Config::default();
+        let theme = Theme::pick("colorblind", &config, true).unwrap();
+        assert_eq!(theme, Theme::built_in("monochrome").unwrap());
+
+        // Nothing in it has a colour
+        let styles = [
+            theme.text,
+            theme.accent,
+            theme.correct,
+            theme.wrong,
+            theme.warning,
+            theme.key,
+        ];
+        assert!(
+            styles
+                .iter()
+                .chain(&theme.heat)
+                .all(|s| s.fg.is_none() && s.bg.is_none())
+        );
+        assert_ne!(theme.correct, theme.wrong);
+    }
+
+    #[test]
+    fn test_user_theme() {
+        let config = Config::parse(
+            "test.conf",
+            "[theme.ocean]\ncorrect = #0088ff bold\nbase = high-contrast\nkey = black on white\n",
+        )
+        .unwrap();
+
+        assert_eq!(Theme::names(&config).last(), Some(&"ocean"));
+        let theme = Theme::pick("ocean", &config, false).unwrap();
+        let high_contrast = Theme::built_in("high-contrast").unwrap();
+        assert_eq!(
+            theme.correct,
+            Style::default()
+                .fg(Color::Rgb(0, 136, 255))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            theme.key,
+            Style::default().fg(Color::Black).bg(Color::White)
+        );
+        assert_eq!(theme.wrong, high_contrast.wrong);
+
+        // Unknown names fall back to the default theme
+        assert_eq!(
+            Theme::pick("gone", &config, false).unwrap(),
+            Theme::default()
+        );
+    }
+
+    #[test]
+    fn test_user_theme_errors() {
+        let error = |text: &str| {
+            let config = Config::parse("test.conf", text).unwrap();
+            Theme::pick("x", &config, false).unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            error("[theme.x]\ncorrect = sparkly\n"),
+            "test.conf:2: bad style `sparkly`"
+        );
+        assert_eq!(
+            error("[theme.x]\nshiny = red\n"),
+            "test.conf:2: unknown theme style `shiny`"
+        );
+        assert_eq!(
+            error("[theme.x]\nbase = x\n"),
+            "test.conf:2: unknown base theme `x`"
+        );
+        assert_eq!(
+            error("[theme.x]\nheat = red, blue\n"),
+            "test.conf:2: `heat` needs five comma-separated styles"
+        );
+    }
+}
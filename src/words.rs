@@ -0,0 +1,389 @@
+// Word list commands
+// Browse and curate the deck from the command line, as text or JSON
+
+use crate::core::{stats::percent, utils};
+use crate::db::{models::Word, queries};
+use crate::dict::Dictionaries;
+use crate::export::{self, Filter};
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use rusqlite::Connection;
+use rusqlite::ffi;
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Sort {
+    /// Group, then the order words were added
+    #[default]
+    Group,
+    /// Alphabetical
+    Word,
+    /// Lowest accuracy first, unseen words last
+    Accuracy,
+    /// Most reviewed first
+    Seen,
+    /// Most recently reviewed first
+    Recent,
+}
+
+pub fn list(conn: &Connection, filter: &Filter, sort: Sort) -> Result<Vec<Word>> {
+    let mut words = export::select_words(conn, filter)?;
+    match sort {
+        Sort::Group => {}
+        Sort::Word => words.sort_by_key(|w| w.word.to_lowercase()),
+        Sort::Accuracy => words.sort_by(|a, b| match (accuracy(a), accuracy(b)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        }),
+        Sort::Seen => words.sort_by_key(|w| std::cmp::Reverse(w.times_seen)),
+        Sort::Recent => words.sort_by_key(|w| std::cmp::Reverse(w.last_seen)),
+    }
+    Ok(words)
+}
+
+pub fn show(conn: &Connection, word: &str) -> Result<Word> {
+    match queries::fetch_word(conn, word)? {
+        Some(found) => Ok(found),
+        None => bail!("\"{word}\" is not in the word list"),
+    }
+}
+
+/// Changes for `edit`; fields left as `None` are kept.
+#[derive(Debug, Clone, Default)]
+pub struct Edit {
+    pub word: Option<String>,
+    pub definition: Option<String>,
+    pub tags: Option<String>,
+    pub example: Option<String>,
+}
+
+impl Edit {
+    fn is_empty(&self) -> bool {
+        self.word.is_none()
+            && self.definition.is_none()
+            && self.tags.is_none()
+            && self.example.is_none()
+    }
+}
+
+/// Add `word` to `group`. Without a `definition` the first one found in
+/// `dictionaries` is used.
+pub fn add(
+    conn: &Connection,
+    word: &str,
+    definition: Option<&str>,
+    group: i32,
+    dictionaries: &Dictionaries,
+) -> Result<Word> {
+    let word = validate_word(word)?;
+    validate_group(group)?;
+    if let Some(existing) = queries::fetch_word(conn, &word)? {
+        bail!(
+            "\"{}\" already exists in Group {}",
+            existing.word,
+            existing.group_id
+        );
+    }
+
+    let definition = match definition {
+        Some(definition) => definition.trim().to_string(),
+        None => match dictionaries.first_definition(&word) {
+            Some(definition) => definition,
+            None => bail!("\"{word}\" not found in any dictionary, pass a definition"),
+        },
+    };
+    queries::insert_word(conn, &word, &definition, group, "")?;
+    show(conn, &word)
+}
+
+pub fn edit(conn: &Connection, word: &str, changes: &Edit) -> Result<Word> {
+    if changes.is_empty() {
+        bail!("nothing to change, pass --rename, --definition, --tags or --example");
+    }
+
+    let mut current = show(conn, word)?;
+    if let Some(new) = &changes.word {
+        let new = validate_word(new)?;
+        if let Some(existing) = queries::fetch_word(conn, &new)?
+            && existing.id != current.id
+        {
+            bail!(
+                "cannot rename to \"{}\": that word already exists",
+                existing.word
+            );
+        }
+        current.word = new;
+    }
+    if let Some(definition) = &changes.definition {
+        current.definition = definition.trim().to_string();
+    }
+    if let Some(tags) = &changes.tags {
+        current.tags = tags.trim().to_string();
+    }
+    if let Some(example) = &changes.example {
+        let example = example.trim();
+        current.example = (!example.is_empty()).then(|| example.to_string());
+    }
+
+    save(conn, &current)?;
+    Ok(current)
+}
+
+pub fn remove(conn: &Connection, word: &str) -> Result<Word> {
+    let word = show(conn, word)?;
+    queries::delete_word(conn, word.id)?;
+    Ok(word)
+}
+
+pub fn set_marked(conn: &Connection, word: &str, marked: bool) -> Result<Word> {
+    let mut word = show(conn, word)?;
+    word.marked = marked;
+    save(conn, &word)?;
+    Ok(word)
+}
+
+pub fn move_to(conn: &Connection, word: &str, group: i32) -> Result<Word> {
+    validate_group(group)?;
+    let mut word = show(conn, word)?;
+    word.group_id = group;
+    save(conn, &word)?;
+    Ok(word)
+}
+
+/// Write back an edited word, turning a unique constraint failure into a
+/// readable message.
+fn save(conn: &Connection, word: &Word) -> Result<()> {
+    queries::update_word(conn, word).map_err(|e| match e.downcast_ref::<rusqlite::Error>() {
+        Some(rusqlite::Error::SqliteFailure(f, _))
+            if f.extended_code == ffi::SQLITE_CONSTRAINT_UNIQUE =>
+        {
+            anyhow::anyhow!("\"{}\" already exists", word.word)
+        }
+        _ => e,
+    })
+}
+
+fn validate_word(word: &str) -> Result<String> {
+    let word = word.split_whitespace().collect::<Vec<_>>().join(" ");
+    if word.is_empty() {
+        bail!("the word cannot be empty");
+    }
+    Ok(word)
+}
+
+fn validate_group(group: i32) -> Result<()> {
+    if group < 1 {
+        bail!("groups are numbered from 1");
+    }
+    Ok(())
+}
+
+/// One line per word: mark, word, group and accuracy.
+pub fn write_table<W: Write>(words: &[Word], out: &mut W) -> Result<()> {
+    for w in words {
+        writeln!(
+            out,
+            "{} {:<24} Group {:<4} {:>3}/{:<3} {:>4}",
+            if w.marked { "*" } else { " " },
+            w.word,
+            w.group_id,
+            w.success_count,
+            w.times_seen,
+            percent(accuracy(w))
+        )?;
+    }
+    Ok(())
+}
+
+/// Every field of a word, one per line.
+pub fn write_details<W: Write>(word: &Word, out: &mut W) -> Result<()> {
+    writeln!(out, "{}", word.word)?;
+    writeln!(
+        out,
+        "  Definition: {}",
+        word.definition.replace('\n', "\n              ")
+    )?;
+    if let Some(example) = &word.example {
+        writeln!(out, "  Example:    \"{example}\"")?;
+    }
+    writeln!(out, "  Group:      {}", word.group_id)?;
+    if !word.tags.is_empty() {
+        writeln!(out, "  Tags:       {}", word.tags)?;
+    }
+    writeln!(
+        out,
+        "  Marked:     {}",
+        if word.marked { "yes" } else { "no" }
+    )?;
+    writeln!(
+        out,
+        "  Reviews:    {}/{} correct ({})",
+        word.success_count,
+        word.times_seen,
+        percent(accuracy(word))
+    )?;
+    writeln!(
+        out,
+        "  Last seen:  {}",
+        utils::relative_time(word.last_seen)
+    )?;
+    Ok(())
+}
+
+fn accuracy(word: &Word) -> Option<f64> {
+    (word.times_seen > 0).then(|| word.success_count as f64 / word.times_seen as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::init_db;
+
+    fn setup() -> Connection {
+        let conn = init_db(":memory:").unwrap();
+        for (word, definition, group, seen, correct) in [
+            ("austere", "strict and stern", 1, 4, 1),
+            ("abound", "be plentiful", 1, 0, 0),
+            ("belie", "contradict; be austere about", 2, 2, 2),
+        ] {
+            queries::insert_word(&conn, word, definition, group, "").unwrap();
+            queries::set_review_counts(&conn, word, seen, correct).unwrap();
+        }
+        conn
+    }
+
+    fn words(list: &[Word]) -> Vec<&str> {
+        list.iter().map(|w| w.word.as_str()).collect()
+    }
+
+    #[test]
+    fn test_list_sorting() {
+        let conn = setup();
+        let all = Filter::default();
+
+        assert_eq!(
+            words(&list(&conn, &all, Sort::Group).unwrap()),
+            vec!["austere", "abound", "belie"]
+        );
+        assert_eq!(
+            words(&list(&conn, &all, Sort::Word).unwrap()),
+            vec!["abound", "austere", "belie"]
+        );
+        assert_eq!(
+            words(&list(&conn, &all, Sort::Accuracy).unwrap()),
+            vec!["austere", "belie", "abound"]
+        );
+    }
+
+    #[test]
+    fn test_show_ignores_case() {
+        let conn = setup();
+
+        assert_eq!(show(&conn, "BELIE").unwrap().group_id, 2);
+        assert!(show(&conn, "missing").is_err());
+    }
+
+    #[test]
+    fn test_search_prefers_word_matches() {
+        let conn = setup();
+
+        let found = queries::search_words(&conn, "aus").unwrap();
+
+        assert_eq!(words(&found), vec!["austere", "belie"]);
+    }
+
+    #[test]
+    fn test_details_text() {
+        let conn = setup();
+        let mut out = Vec::new();
+
+        write_details(&show(&conn, "austere").unwrap(), &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Reviews:    1/4 correct (25%)"));
+        assert!(text.contains("Marked:     no"));
+    }
+
+    #[test]
+    fn test_add_rejects_duplicates_ignoring_case() {
+        let conn = setup();
+
+        let none = Dictionaries::default();
+
+        let added = add(&conn, "  laconic ", Some("terse"), 3, &none).unwrap();
+        assert_eq!((added.word.as_str(), added.group_id), ("laconic", 3));
+
+        let err = add(&conn, "Austere", Some("again"), 1, &none).unwrap_err();
+        assert_eq!(err.to_string(), "\"austere\" already exists in Group 1");
+        assert!(add(&conn, "new", Some("x"), 0, &none).is_err());
+    }
+
+    #[test]
+    fn test_add_looks_up_missing_definition() {
+        let conn = setup();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("wn.index"), "laconic\tA\tP\n").unwrap();
+        std::fs::write(dir.path().join("wn.dict"), "using few words").unwrap();
+        queries::add_dictionary(&conn, dir.path().join("wn.index").to_str().unwrap()).unwrap();
+        let dictionaries = Dictionaries::load(&conn).unwrap();
+
+        let added = add(&conn, "laconic", None, 1, &dictionaries).unwrap();
+        assert_eq!(added.definition, "using few words");
+
+        let err = add(&conn, "terse", None, 1, &dictionaries).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"terse\" not found in any dictionary, pass a definition"
+        );
+    }
+
+    #[test]
+    fn test_edit_fields_and_rename_conflict() {
+        let conn = setup();
+
+        let changes = Edit {
+            definition: Some("severe".into()),
+            example: Some("An austere room.".into()),
+            ..Default::default()
+        };
+        let edited = edit(&conn, "austere", &changes).unwrap();
+        assert_eq!(edited.definition, "severe");
+        assert_eq!(edited.example.as_deref(), Some("An austere room."));
+        // Stats are left alone
+        assert_eq!(edited.times_seen, 4);
+
+        let rename = Edit {
+            word: Some("BELIE".into()),
+            ..Default::default()
+        };
+        assert!(edit(&conn, "austere", &rename).is_err());
+        assert!(edit(&conn, "austere", &Edit::default()).is_err());
+    }
+
+    #[test]
+    fn test_edit_without_changes_names_the_flags() {
+        let conn = setup();
+
+        let err = edit(&conn, "austere", &Edit::default()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "nothing to change, pass --rename, --definition, --tags or --example"
+        );
+    }
+
+    #[test]
+    fn test_mark_move_and_remove() {
+        let conn = setup();
+
+        assert!(set_marked(&conn, "abound", true).unwrap().marked);
+        assert_eq!(move_to(&conn, "abound", 5).unwrap().group_id, 5);
+        assert!(show(&conn, "abound").unwrap().marked);
+
+        remove(&conn, "abound").unwrap();
+        assert!(show(&conn, "abound").is_err());
+        assert_eq!(
+            remove(&conn, "abound").unwrap_err().to_string(),
+            "\"abound\" is not in the word list"
+        );
+    }
+}